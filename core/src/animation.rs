@@ -0,0 +1,203 @@
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Range;
+
+/// Quality/speed trade-off of the GIF colour quantizer, from 1 (best) to 30 (fastest).
+const GIF_SPEED: i32 = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnimationFormat {
+    #[default]
+    Gif,
+    Apng,
+}
+
+impl AnimationFormat {
+    pub const ALL: [AnimationFormat; 2] = [AnimationFormat::Gif, AnimationFormat::Apng];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Gif => "GIF",
+            Self::Apng => "APNG",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Gif => "gif",
+            Self::Apng => "png",
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            Self::Gif => "image/gif",
+            Self::Apng => "image/apng",
+        }
+    }
+}
+
+/// Number of frames needed to cover `time_span` at `fps`, both ends included.
+pub fn frame_count(time_span: &Range, fps: f32) -> u32 {
+    ((time_span.max - time_span.min) * fps).ceil() as u32 + 1
+}
+
+/// Output buffer shared with the encoder, as the PNG writer never gives its sink back.
+#[derive(Clone, Default)]
+struct Buffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+enum Inner {
+    Gif(gif::Encoder<Buffer>),
+    Apng(png::Writer<Buffer>),
+}
+
+/// Encodes RGBA frames of a fixed size into an animation that loops forever.
+pub struct AnimationEncoder {
+    buffer: Buffer,
+    inner: Inner,
+    size: (u16, u16),
+    fps: f32,
+}
+
+impl AnimationEncoder {
+    pub fn new(
+        format: AnimationFormat,
+        size: (u16, u16),
+        frames: u32,
+        fps: f32,
+    ) -> Result<Self, String> {
+        let buffer = Buffer::default();
+        let (width, height) = size;
+        let inner = match format {
+            AnimationFormat::Gif => {
+                let mut encoder = gif::Encoder::new(buffer.clone(), width, height, &[])
+                    .map_err(|e| e.to_string())?;
+                encoder
+                    .set_repeat(gif::Repeat::Infinite)
+                    .map_err(|e| e.to_string())?;
+                Inner::Gif(encoder)
+            }
+            AnimationFormat::Apng => {
+                let mut encoder = png::Encoder::new(buffer.clone(), width as u32, height as u32);
+                encoder.set_color(png::ColorType::Rgba);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder.set_compression(png::Compression::Fast);
+                encoder.set_animated(frames, 0).map_err(|e| e.to_string())?;
+                encoder
+                    .set_frame_delay((1000. / fps).round() as u16, 1000)
+                    .map_err(|e| e.to_string())?;
+                encoder
+                    .set_dispose_op(png::DisposeOp::Background)
+                    .map_err(|e| e.to_string())?;
+                encoder
+                    .set_blend_op(png::BlendOp::Source)
+                    .map_err(|e| e.to_string())?;
+                Inner::Apng(encoder.write_header().map_err(|e| e.to_string())?)
+            }
+        };
+        Ok(Self {
+            buffer,
+            inner,
+            size,
+            fps,
+        })
+    }
+
+    /// Appends a frame of `width * height` unpremultiplied RGBA pixels.
+    pub fn push(&mut self, rgba: &mut [u8]) -> Result<(), String> {
+        let (width, height) = self.size;
+        if rgba.len() != width as usize * height as usize * 4 {
+            return Err(format!(
+                "frame has {} bytes, expected {}x{} RGBA",
+                rgba.len(),
+                width,
+                height
+            ));
+        }
+
+        match &mut self.inner {
+            Inner::Gif(encoder) => {
+                let mut frame = gif::Frame::from_rgba_speed(width, height, rgba, GIF_SPEED);
+                frame.delay = (100. / self.fps).round() as u16;
+                frame.dispose = gif::DisposalMethod::Background;
+                encoder.write_frame(&frame).map_err(|e| e.to_string())
+            }
+            Inner::Apng(writer) => writer.write_image_data(rgba).map_err(|e| e.to_string()),
+        }
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>, String> {
+        match self.inner {
+            Inner::Gif(encoder) => drop(encoder.into_inner().map_err(|e| e.to_string())?),
+            Inner::Apng(writer) => writer.finish().map_err(|e| e.to_string())?,
+        }
+        Ok(self.buffer.0.take())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(format: AnimationFormat) -> Vec<u8> {
+        let mut encoder = AnimationEncoder::new(format, (4, 2), 3, 10.).unwrap();
+        for i in 0..3u8 {
+            let mut rgba = [i * 80, 0, 0, 255].repeat(8);
+            encoder.push(&mut rgba).unwrap();
+        }
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_frame_count() {
+        let time_span = Range {
+            min: 0.0,
+            max: 2.05,
+        };
+        assert_eq!(frame_count(&time_span, 10.), 22);
+    }
+
+    #[test]
+    fn test_encode_gif() {
+        let bytes = frames(AnimationFormat::Gif);
+        assert!(bytes.starts_with(b"GIF89a"));
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(bytes.as_slice())
+            .unwrap();
+        let mut count = 0;
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 10);
+            count += 1;
+        }
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_encode_apng() {
+        let bytes = frames(AnimationFormat::Apng);
+        let reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+        let control = reader.info().animation_control.unwrap();
+        assert_eq!(control.num_frames, 3);
+        assert_eq!(control.num_plays, 0);
+    }
+
+    #[test]
+    fn test_push_rejects_wrong_size() {
+        let mut encoder = AnimationEncoder::new(AnimationFormat::Gif, (4, 2), 1, 10.).unwrap();
+        assert!(encoder.push(&mut [0; 4]).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::{Cursor, Read};
+
+use crate::LibraryShot;
+
+/// Outcome of one shot file found in an archive.
+#[derive(Debug)]
+pub struct ArchiveEntry {
+    pub name: String,
+    pub shot: Result<LibraryShot, String>,
+}
+
+/// Whether `path` looks like a shot of the DE1 history folder, leaving out
+/// folders and the metadata files archivers add.
+fn is_shot_file(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    let name = lower.rsplit('/').next().unwrap_or_default();
+    !lower.starts_with("__macosx/")
+        && !name.starts_with('.')
+        && (name.ends_with(".json") || name.ends_with(".shot"))
+}
+
+/// Reads every shot file of a zip archive. Only an unreadable archive is an
+/// error; a bad entry is reported on its own and the others still load.
+pub fn read_archive(bytes: &[u8]) -> Result<Vec<ArchiveEntry>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(|e| e.to_string())?;
+    let mut entries = vec![];
+    for i in 0..archive.len() {
+        let mut file = match archive.by_index(i) {
+            Ok(file) => file,
+            Err(e) => {
+                entries.push(ArchiveEntry {
+                    name: format!("entry {}", i),
+                    shot: Err(e.to_string()),
+                });
+                continue;
+            }
+        };
+        if file.is_dir() || !is_shot_file(file.name()) {
+            continue;
+        }
+
+        let path = file.name().to_string();
+        let name = path.rsplit('/').next().unwrap_or(&path).to_string();
+        let mut text = String::default();
+        let shot = match file.read_to_string(&mut text) {
+            Ok(_) => LibraryShot::parse(&name, &text),
+            Err(e) => Err(e.to_string()),
+        };
+        entries.push(ArchiveEntry { name: path, shot });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    use crate::models::json::{SHOT1, SHOT2};
+
+    fn archive(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(vec![]));
+        let options = zip::write::FileOptions::default();
+        for (name, contents) in files {
+            if name.ends_with('/') {
+                writer.add_directory(*name, options).unwrap();
+            } else {
+                writer.start_file(*name, options).unwrap();
+                writer.write_all(contents.as_bytes()).unwrap();
+            }
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_is_shot_file() {
+        assert!(is_shot_file("history/20230601T101923.json"));
+        assert!(is_shot_file("history/20230601T101923.SHOT"));
+        assert!(!is_shot_file("history/.DS_Store"));
+        assert!(!is_shot_file("history/._20230601T101923.json"));
+        assert!(!is_shot_file("__MACOSX/history/20230601T101923.json"));
+        assert!(!is_shot_file("history/readme.txt"));
+    }
+
+    #[test]
+    fn test_read_archive_reports_each_entry() {
+        let bytes = archive(&[
+            ("history/", ""),
+            ("history/shot1.json", SHOT1),
+            ("history/broken.json", "{ \"version\": "),
+            ("history/.DS_Store", "junk"),
+            ("history/shot2.json", SHOT2),
+        ]);
+        let entries = read_archive(&bytes).unwrap();
+        let names: Vec<_> = entries.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "history/shot1.json",
+                "history/broken.json",
+                "history/shot2.json"
+            ]
+        );
+        assert!(entries[0].shot.is_ok());
+        assert!(entries[1].shot.is_err());
+        assert_eq!(entries[2].shot.as_ref().unwrap().file_name, "shot2.json");
+    }
+
+    #[test]
+    fn test_read_archive_rejects_other_files() {
+        assert!(read_archive(b"not a zip").is_err());
+    }
+}
@@ -0,0 +1,381 @@
+use std::fmt::Write;
+use std::rc::Rc;
+
+use crate::{
+    models::{ChartData, SeriesKind},
+    Axis, Overlay, Range, Rect, Settings, Units, AXIS_LABEL_SIZE, HUD_FONT_SIZE, TICK_LENGTH,
+};
+
+/// Drawing mode of the shapes: coordinates are in quarters of a pixel.
+const DRAWING_SCALE: i32 = 3;
+const SUBPIXELS: f32 = 4.0;
+/// Control points of a quarter circle approximated by a cubic Bézier.
+const KAPPA: f32 = 0.552_284_8;
+
+const LAYER_PANEL: u8 = 0;
+const LAYER_AXIS: u8 = 1;
+const LAYER_STROKES: u8 = 2;
+const LAYER_TEXT: u8 = 3;
+
+/// Parses the CSS colours used by the themes into RGB and alpha.
+fn parse_color(css: &str) -> ((u8, u8, u8), f32) {
+    let css = css.trim();
+    if let Some(hex) = css.strip_prefix('#') {
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2).unwrap_or("ff"), 16);
+        if let (Ok(r), Ok(g), Ok(b)) = (channel(0), channel(2), channel(4)) {
+            return ((r, g, b), 1.0);
+        }
+    }
+    if let Some(args) = css
+        .strip_prefix("rgba(")
+        .or_else(|| css.strip_prefix("rgb("))
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        let args: Vec<f32> = args
+            .split(',')
+            .filter_map(|arg| arg.trim().parse().ok())
+            .collect();
+        if let [r, g, b, rest @ ..] = args.as_slice() {
+            let alpha = rest.first().copied().unwrap_or(1.0);
+            return ((*r as u8, *g as u8, *b as u8), alpha);
+        }
+    }
+    match css {
+        "darkgreen" => ((0x00, 0x64, 0x00), 1.0),
+        "darkred" => ((0x8b, 0x00, 0x00), 1.0),
+        "red" => ((0xff, 0x00, 0x00), 1.0),
+        "black" => ((0x00, 0x00, 0x00), 1.0),
+        _ => ((0xff, 0xff, 0xff), 1.0),
+    }
+}
+
+/// `\1c` and `\1a` override tags for a CSS colour drawn at `opacity`.
+fn fill(css: &str, opacity: f32) -> String {
+    let ((r, g, b), alpha) = parse_color(css);
+    let alpha = 255 - (alpha * opacity * 255.).round().clamp(0., 255.) as u8;
+    format!(r"\1c&H{:02X}{:02X}{:02X}&\1a&H{:02X}&", b, g, r, alpha)
+}
+
+/// `h:mm:ss.cc`
+fn timestamp(seconds: f32) -> String {
+    let cs = (seconds.max(0.) * 100.).round() as u64;
+    format!(
+        "{}:{:02}:{:02}.{:02}",
+        cs / 360_000,
+        cs / 6_000 % 60,
+        cs / 100 % 60,
+        cs % 100
+    )
+}
+
+/// Braces and backslashes would start override blocks.
+fn escape(text: &str) -> String {
+    text.replace('\\', "/").replace('{', "(").replace('}', ")")
+}
+
+/// Vector drawing commands in [`DRAWING_SCALE`] coordinates.
+#[derive(Default)]
+struct Drawing(String);
+
+impl Drawing {
+    fn point(&mut self, (x, y): (f32, f32)) {
+        let _ = write!(
+            self.0,
+            " {} {}",
+            (x * SUBPIXELS).round() as i32,
+            (y * SUBPIXELS).round() as i32
+        );
+    }
+
+    fn polygon(&mut self, points: &[(f32, f32)]) {
+        for (i, point) in points.iter().enumerate() {
+            self.0.push_str(if i == 0 { " m" } else { " l" });
+            self.point(*point);
+        }
+    }
+
+    fn rect(&mut self, rect: &Rect) {
+        let Rect {
+            x,
+            y,
+            width,
+            height,
+        } = *rect;
+        self.polygon(&[
+            (x, y),
+            (x + width, y),
+            (x + width, y + height),
+            (x, y + height),
+        ]);
+    }
+
+    fn rounded_rect(&mut self, rect: &Rect, radius: f32) {
+        let Rect {
+            x,
+            y,
+            width,
+            height,
+        } = *rect;
+        let r = radius.min(width / 2.).min(height / 2.);
+        let k = r * (1. - KAPPA);
+        let (right, bottom) = (x + width, y + height);
+
+        self.polygon(&[(x + r, y), (right - r, y)]);
+        self.bezier((right - k, y), (right, y + k), (right, y + r));
+        self.line_to((right, bottom - r));
+        self.bezier(
+            (right, bottom - k),
+            (right - k, bottom),
+            (right - r, bottom),
+        );
+        self.line_to((x + r, bottom));
+        self.bezier((x + k, bottom), (x, bottom - k), (x, bottom - r));
+        self.line_to((x, y + r));
+        self.bezier((x, y + k), (x + k, y), (x + r, y));
+    }
+
+    fn line_to(&mut self, point: (f32, f32)) {
+        self.0.push_str(" l");
+        self.point(point);
+    }
+
+    fn bezier(&mut self, c1: (f32, f32), c2: (f32, f32), to: (f32, f32)) {
+        self.0.push_str(" b");
+        self.point(c1);
+        self.point(c2);
+        self.point(to);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+struct Script {
+    buf: String,
+}
+
+impl Script {
+    fn new(size: (f32, f32)) -> Self {
+        let mut buf = String::default();
+        let _ = write!(
+            buf,
+            "[Script Info]\n\
+             ScriptType: v4.00+\n\
+             PlayResX: {}\n\
+             PlayResY: {}\n\
+             WrapStyle: 2\n\
+             ScaledBorderAndShadow: yes\n\
+             \n\
+             [V4+ Styles]\n\
+             Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+             Style: Shape,Sans,{hud},&H00FFFFFF,&H00FFFFFF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,0,0,7,0,0,0,1\n\
+             Style: Hud,Sans,{hud},&H00FFFFFF,&H00FFFFFF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,0,0,1,0,0,0,1\n\
+             Style: Axis,Sans,{axis},&H00FFFFFF,&H00FFFFFF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,0,0,2,0,0,0,1\n\
+             \n\
+             [Events]\n\
+             Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+            size.0.round(),
+            size.1.round(),
+            hud = HUD_FONT_SIZE,
+            axis = AXIS_LABEL_SIZE,
+        );
+        Self { buf }
+    }
+
+    fn dialogue(&mut self, layer: u8, span: (f32, f32), style: &str, text: &str) {
+        let _ = writeln!(
+            self.buf,
+            "Dialogue: {},{},{},{},,0,0,0,,{}",
+            layer,
+            timestamp(span.0),
+            timestamp(span.1),
+            style,
+            text
+        );
+    }
+
+    fn shape(&mut self, layer: u8, span: (f32, f32), fill: &str, drawing: &Drawing) {
+        if drawing.is_empty() {
+            return;
+        }
+        let text = format!(
+            r"{{\an7\pos(0,0){}\p{}}}{}{{\p0}}",
+            fill,
+            DRAWING_SCALE,
+            drawing.0.trim_start()
+        );
+        self.dialogue(layer, span, "Shape", &text);
+    }
+
+    fn text(
+        &mut self,
+        layer: u8,
+        span: (f32, f32),
+        style: &str,
+        position: (f32, f32),
+        fill: &str,
+        text: &str,
+    ) {
+        let text = format!(
+            r"{{\pos({:.1},{:.1}){}}}{}",
+            position.0,
+            position.1,
+            fill,
+            escape(text)
+        );
+        self.dialogue(layer, span, style, &text);
+    }
+
+    /// Panel, axis and tick labels, which don't move during the shot.
+    fn push_static(&mut self, span: (f32, f32), overlay: &Overlay) {
+        if let Some(panel) = &overlay.panel {
+            let mut drawing = Drawing::default();
+            drawing.rounded_rect(&panel.rect, panel.radius);
+            self.shape(
+                LAYER_PANEL,
+                span,
+                &fill(panel.fill, overlay.opacity),
+                &drawing,
+            );
+        }
+
+        let Axis { from, to, ticks } = &overlay.axis;
+        let mut drawing = Drawing::default();
+        drawing.rect(&Rect::new(from.0, from.1 - 0.5, to.0 - from.0, 1.));
+        for tick in ticks.iter() {
+            drawing.rect(&Rect::new(
+                tick.x - 0.5,
+                from.1 + 0.5,
+                1.,
+                TICK_LENGTH - 0.5,
+            ));
+        }
+        let color = fill(overlay.theme.axis, overlay.opacity);
+        self.shape(LAYER_AXIS, span, &color, &drawing);
+
+        let color = fill(overlay.theme.text, overlay.opacity);
+        for tick in ticks.iter() {
+            let position = (tick.x, from.1 + TICK_LENGTH + AXIS_LABEL_SIZE);
+            self.text(LAYER_TEXT, span, "Axis", position, &color, &tick.label);
+        }
+    }
+
+    /// Strokes and readouts, which change every frame.
+    fn push_frame(&mut self, span: (f32, f32), overlay: &Overlay) {
+        for stroke in overlay.strokes.iter() {
+            let mut drawing = Drawing::default();
+            drawing.polygon(&stroke.path.outline(stroke.width));
+            let color = fill(stroke.color, overlay.opacity * stroke.opacity);
+            self.shape(LAYER_STROKES, span, &color, &drawing);
+        }
+
+        for line in overlay.hud.lines.iter() {
+            let color = fill(overlay.theme.color(line.kind), overlay.opacity);
+            self.text(LAYER_TEXT, span, "Hud", line.position, &color, &line.text);
+        }
+    }
+}
+
+/// Advanced SubStation script that draws the overlay of a frame of `size`,
+/// refreshed every `interval` seconds, for burning in with `ffmpeg -vf ass=`.
+/// Arguments are the same as [`Overlay::build`]; events are shifted by `settings.offset`.
+pub fn to_ass(
+    charts: &[(SeriesKind, Rc<ChartData>)],
+    units: &Units,
+    time_span: &Range,
+    settings: &Settings,
+    size: (f32, f32),
+    interval: f32,
+) -> String {
+    let mut script = Script::new(size);
+    if interval <= 0. {
+        return script.buf;
+    }
+
+    let offset = settings.offset;
+    let overlay = |t| Overlay::build(charts, units, time_span, t, size, settings);
+    let whole = (time_span.min + offset, time_span.max + offset);
+    if whole.1 <= 0. {
+        return script.buf;
+    }
+    script.push_static(whole, &overlay(time_span.min));
+
+    let count = ((time_span.max - time_span.min) / interval).ceil().max(1.) as usize;
+    for i in 0..count {
+        let t = time_span.min + interval * i as f32;
+        let span = (t + offset, (t + interval).min(time_span.max) + offset);
+        if span.1 <= 0. {
+            continue;
+        }
+        script.push_frame(span, &overlay(t));
+    }
+    script.buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::{
+        json::{ShotDataJson, SHOT2},
+        ShotData,
+    };
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("#1f77ea"), ((0x1f, 0x77, 0xea), 1.0));
+        assert_eq!(parse_color("rgba(24, 24, 32, 0.6)"), ((24, 24, 32), 0.6));
+        assert_eq!(parse_color("darkgreen"), ((0, 0x64, 0), 1.0));
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(fill("#1f77ea", 1.0), r"\1c&HEA771F&\1a&H00&");
+        assert_eq!(fill("rgba(0, 0, 0, 0.5)", 0.5), r"\1c&H000000&\1a&HBF&");
+    }
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(timestamp(3725.456), "1:02:05.46");
+        assert_eq!(timestamp(-1.0), "0:00:00.00");
+    }
+
+    #[test]
+    fn test_rounded_rect() {
+        let mut drawing = Drawing::default();
+        drawing.rounded_rect(&Rect::new(0., 0., 10., 10.), 0.);
+        assert_eq!(
+            drawing.0,
+            " m 0 0 l 40 0 b 40 0 40 0 40 0 l 40 40 b 40 40 40 40 40 40 l 0 40 b 0 40 0 40 0 40 l 0 0 b 0 0 0 0 0 0"
+        );
+    }
+
+    #[test]
+    fn test_to_ass() {
+        let data: ShotData = serde_json::from_str::<ShotDataJson>(SHOT2).unwrap().into();
+        let units = Units::from_shot(&data);
+        let charts = ChartData::all_in(&data, &units);
+        let time_span = Range::from_series(&data.elapsed);
+        let settings = Settings {
+            panel: true,
+            offset: 2.0,
+            ..Default::default()
+        };
+        let ass = to_ass(&charts, &units, &time_span, &settings, (1080., 1920.), 1.0);
+
+        assert!(
+            ass.starts_with("[Script Info]\nScriptType: v4.00+\nPlayResX: 1080\nPlayResY: 1920\n")
+        );
+        let events: Vec<&str> = ass.lines().filter(|l| l.starts_with("Dialogue:")).collect();
+        assert!(events[0].starts_with("Dialogue: 0,0:00:02.00,"));
+        assert!(events[0].contains(r"\p3}m "));
+        let frames = ((time_span.max - time_span.min) / 1.0).ceil() as usize;
+        let readouts = events.iter().filter(|e| e.contains(",Hud,")).count();
+        assert_eq!(readouts, frames * SeriesKind::ALL.len());
+        assert!(events
+            .iter()
+            .any(|e| e.contains(",Hud,") && e.contains("Pressure ")));
+        assert!(events.last().unwrap().contains(",Hud,"));
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{scale, Rect};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tick {
+    pub x: f32,
+    pub label: String,
+}
+
+/// Time axis along the bottom edge of the chart block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Axis {
+    pub from: (f32, f32),
+    pub to: (f32, f32),
+    pub ticks: Vec<Tick>,
+}
+
+pub const TICK_LENGTH: f32 = 4.0;
+
+impl Axis {
+    /// Places a tick every `step` seconds within `time_domain`.
+    pub fn time(time_domain: (f32, f32), area: &Rect, step: f32) -> Self {
+        let x = scale(time_domain, (area.x, area.x + area.width));
+        let bottom = area.y + area.height;
+
+        let mut ticks = vec![];
+        let mut t = (time_domain.0 / step).ceil() * step;
+        while t <= time_domain.1 {
+            ticks.push(Tick {
+                x: x(t),
+                label: format!("{}s", t),
+            });
+            t += step;
+        }
+
+        Self {
+            from: (area.x, bottom),
+            to: (area.x + area.width, bottom),
+            ticks,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis_time() {
+        let actual = Axis::time((0., 25.), &Rect::new(0., 0., 250., 100.), 10.);
+        let expected = Axis {
+            from: (0., 100.),
+            to: (250., 100.),
+            ticks: vec![
+                Tick {
+                    x: 0.,
+                    label: "0s".into(),
+                },
+                Tick {
+                    x: 100.,
+                    label: "10s".into(),
+                },
+                Tick {
+                    x: 200.,
+                    label: "20s".into(),
+                },
+            ],
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,177 @@
+use std::rc::Rc;
+
+use crate::{
+    models::{ChartData, DataPoint, SeriesKind, ShotData},
+    stats::ShotStats,
+    Range, Units,
+};
+
+/// Moment of the two shots that is lined up on the timeline.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Alignment {
+    #[default]
+    Start,
+    /// When the weight first exceeds [`crate::stats::FIRST_DROPS_WEIGHT`].
+    FirstDrops,
+    /// Start of the `n`-th step as run, counting from 0.
+    Phase(usize),
+}
+
+impl Alignment {
+    /// Every alignment that makes sense for `data`, with a label for each.
+    pub fn options(data: &ShotData) -> Vec<(Self, String)> {
+        let stats = ShotStats::of(data);
+        let mut options = vec![
+            (Self::Start, "Start".to_string()),
+            (Self::FirstDrops, "First drops".to_string()),
+        ];
+        options.extend(stats.phases.iter().enumerate().map(|(i, phase)| {
+            let label = match &phase.name {
+                Some(name) => format!("Step {}: {}", i + 1, name),
+                None => format!("Step {}", i + 1),
+            };
+            (Self::Phase(i), label)
+        }));
+        options
+    }
+
+    fn anchor(&self, stats: &ShotStats) -> Option<f32> {
+        match self {
+            Self::Start => Some(0.),
+            Self::FirstDrops => stats.first_drops_time,
+            Self::Phase(i) => stats.phases.get(*i).map(|phase| phase.start),
+        }
+    }
+
+    /// Seconds to add to the times of `reference` to line it up with `data`,
+    /// or `None` when either shot lacks the moment, e.g. it had no scale.
+    pub fn offset(&self, data: &ShotData, reference: &ShotData) -> Option<f32> {
+        let anchor = self.anchor(&ShotStats::of(data))?;
+        let reference = self.anchor(&ShotStats::of(reference))?;
+        Some(anchor - reference)
+    }
+}
+
+fn has_data(data: &ChartData) -> bool {
+    data.series
+        .iter()
+        .any(|dp| matches!(dp, DataPoint::Present(_)))
+}
+
+/// The series of a shot and of a reference shot, lined up in time and sharing
+/// the value axis of each kind so that the lines can be compared.
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub charts: Vec<(SeriesKind, Rc<ChartData>)>,
+    /// Only the part that overlaps the time span of the shot.
+    pub reference: Vec<(SeriesKind, Rc<ChartData>)>,
+    /// Seconds added to the times of the reference.
+    pub offset: f32,
+}
+
+impl Comparison {
+    /// Lines up the starts when either shot lacks the moment of `alignment`.
+    pub fn new(data: &ShotData, reference: &ShotData, units: &Units, alignment: Alignment) -> Self {
+        let offset = alignment.offset(data, reference).unwrap_or_default();
+        let time_span = if data.elapsed.is_empty() {
+            Range { min: 0., max: 0. }
+        } else {
+            Range::from_series(&data.elapsed)
+        };
+
+        let mut charts = vec![];
+        let mut references = vec![];
+        for kind in SeriesKind::ALL {
+            let mut current = ChartData::of_in(kind, data, units);
+            let mut other = ChartData::of_in(kind, reference, units).shift(offset, &time_span);
+            let range = match (has_data(&current), has_data(&other)) {
+                (true, true) => current.range.union(&other.range),
+                (false, true) => other.range.clone(),
+                _ => current.range.clone(),
+            };
+            current.range = range.clone();
+            other.range = range;
+            charts.push((kind, Rc::new(current)));
+            references.push((kind, Rc::new(other)));
+        }
+
+        Self {
+            charts,
+            reference: references,
+            offset,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::json::{ShotDataJson, SHOT1, SHOT2};
+
+    fn shots() -> (ShotData, ShotData) {
+        let shot = |json| serde_json::from_str::<ShotDataJson>(json).unwrap().into();
+        (shot(SHOT2), shot(SHOT1))
+    }
+
+    #[test]
+    fn test_alignment_offset() {
+        let (data, reference) = shots();
+        assert_eq!(Alignment::Start.offset(&data, &reference), Some(0.));
+        let offset = Alignment::FirstDrops.offset(&data, &reference).unwrap();
+        assert!((offset - (18.495 - 6.57)).abs() < 0.001);
+        let offset = Alignment::Phase(1).offset(&data, &reference).unwrap();
+        let start = ShotStats::of(&data).phases[1].start;
+        assert!((offset - (start - 4.544)).abs() < 0.001);
+        assert_eq!(Alignment::Phase(9).offset(&data, &reference), None);
+    }
+
+    #[test]
+    fn test_alignment_options() {
+        let (_, reference) = shots();
+        let labels: Vec<_> = Alignment::options(&reference)
+            .into_iter()
+            .map(|(_, label)| label)
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "Start",
+                "First drops",
+                "Step 1: preinfusion",
+                "Step 2: dynamic bloom",
+                "Step 3: ramp",
+                "Step 4: 6 bar",
+                "Step 5: decline"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comparison_shares_axes() {
+        let (data, reference) = shots();
+        let comparison = Comparison::new(&data, &reference, &Units::METRIC, Alignment::FirstDrops);
+        assert!(0. < comparison.offset);
+        let time_span = Range::from_series(&data.elapsed);
+        for ((kind, current), (other_kind, other)) in
+            comparison.charts.iter().zip(comparison.reference.iter())
+        {
+            assert_eq!(kind, other_kind);
+            assert_eq!(current.range, other.range);
+            assert!(other
+                .series
+                .iter()
+                .all(|dp| time_span.min <= dp.t() && dp.t() <= time_span.max));
+            // The reference starts later, by the offset.
+            assert!(comparison.offset <= other.series[0].t());
+        }
+    }
+
+    #[test]
+    fn test_comparison_falls_back_to_start() {
+        let (data, mut reference) = shots();
+        reference.totals.weight.clear();
+        let comparison = Comparison::new(&data, &reference, &Units::METRIC, Alignment::FirstDrops);
+        assert_eq!(comparison.offset, 0.);
+    }
+}
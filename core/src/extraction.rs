@@ -0,0 +1,271 @@
+use std::ops::RangeInclusive;
+
+use serde_json::Value;
+
+use crate::models::{MetaSection, ShotData};
+use crate::stats::final_weight;
+
+/// Inputs of the extraction yield, as entered in the calculator.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Extraction {
+    /// Ground coffee in, in grams.
+    pub dose: f32,
+    /// Beverage out, in grams.
+    pub beverage: f32,
+    /// Total dissolved solids of the beverage, in percent.
+    pub tds: f32,
+}
+
+impl Extraction {
+    /// The logged values, with the beverage weighed by the scale when none was logged.
+    pub fn of(data: &ShotData) -> Self {
+        let meta = &data.meta;
+        let beverage = Some(meta.beverage)
+            .filter(|beverage| 0. < *beverage)
+            .or_else(|| final_weight(data))
+            .unwrap_or_default();
+        Self {
+            dose: meta.dose,
+            beverage,
+            tds: meta.tds,
+        }
+    }
+
+    /// Extraction yield in percent: the share of the dose that ended up in the cup.
+    pub fn ey(&self) -> Option<f32> {
+        let valid = |value: f32| value.is_finite() && 0. < value;
+        (valid(self.dose) && valid(self.beverage) && valid(self.tds))
+            .then(|| self.beverage * self.tds / self.dose)
+    }
+
+    /// Beverage out per ground coffee in.
+    pub fn ratio(&self) -> Option<f32> {
+        (0. < self.dose && 0. < self.beverage).then(|| self.beverage / self.dose)
+    }
+
+    /// Writes the values and the yield into the metadata of a parsed shot.
+    pub fn apply(&self, meta: &mut MetaSection) {
+        meta.dose = self.dose;
+        meta.beverage = self.beverage;
+        meta.tds = self.tds;
+        meta.ey = self.ey().unwrap_or_default();
+    }
+
+    /// Writes the values and the yield into a DE1 shot file, formatted as the app does.
+    pub fn apply_json(&self, shot: &mut Value) -> Result<(), String> {
+        let meta = shot
+            .get_mut("meta")
+            .and_then(Value::as_object_mut)
+            .ok_or("not a shot file: missing meta")?;
+        meta.insert("in".into(), format!("{:.1}", self.dose).into());
+        meta.insert("out".into(), format!("{:.1}", self.beverage).into());
+        let shot = meta
+            .entry("shot")
+            .or_insert_with(|| Value::Object(Default::default()));
+        let shot = shot
+            .as_object_mut()
+            .ok_or("invalid shot file: meta.shot is not an object")?;
+        shot.insert("tds".into(), format!("{:.2}", self.tds).into());
+        shot.insert(
+            "ey".into(),
+            format!("{:.2}", self.ey().unwrap_or_default()).into(),
+        );
+        Ok(())
+    }
+}
+
+/// Box of a brew control chart where a shot tastes balanced.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrewTarget {
+    /// Strength, in percent TDS.
+    pub tds: RangeInclusive<f32>,
+    /// Extraction yield, in percent.
+    pub ey: RangeInclusive<f32>,
+}
+
+impl BrewTarget {
+    /// The usual target for espresso.
+    pub fn espresso() -> Self {
+        Self {
+            tds: 8.0..=12.0,
+            ey: 18.0..=22.0,
+        }
+    }
+
+    /// Where a shot falls relative to the box, e.g. "strong and under-extracted".
+    pub fn describe(&self, tds: f32, ey: f32) -> &'static str {
+        let strength = if tds < *self.tds.start() {
+            -1
+        } else if *self.tds.end() < tds {
+            1
+        } else {
+            0
+        };
+        let extraction = if ey < *self.ey.start() {
+            -1
+        } else if *self.ey.end() < ey {
+            1
+        } else {
+            0
+        };
+        match (strength, extraction) {
+            (0, 0) => "balanced",
+            (-1, 0) => "weak",
+            (1, 0) => "strong",
+            (0, -1) => "under-extracted",
+            (0, 1) => "over-extracted",
+            (-1, -1) => "weak and under-extracted",
+            (-1, 1) => "weak and over-extracted",
+            (1, -1) => "strong and under-extracted",
+            _ => "strong and over-extracted",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::json::{sample, ShotDataJson, SHOT1};
+
+    fn shot1() -> ShotData {
+        serde_json::from_str::<ShotDataJson>(SHOT1).unwrap().into()
+    }
+
+    #[test]
+    fn test_extraction_of_shot() {
+        let mut data = shot1();
+        assert_eq!(
+            Extraction::of(&data),
+            Extraction {
+                dose: 19.0,
+                beverage: 39.1,
+                tds: 0.0
+            }
+        );
+
+        // Without a logged yield the scale stands in.
+        data.meta.beverage = 0.;
+        assert_eq!(Extraction::of(&data).beverage, 37.3);
+    }
+
+    #[test]
+    fn test_ey() {
+        let extraction = Extraction {
+            dose: 18.0,
+            beverage: 36.0,
+            tds: 10.0,
+        };
+        assert_eq!(extraction.ey(), Some(20.0));
+        assert_eq!(extraction.ratio(), Some(2.0));
+        assert_eq!(
+            Extraction {
+                tds: 0.,
+                ..extraction
+            }
+            .ey(),
+            None
+        );
+        assert_eq!(
+            Extraction {
+                dose: 0.,
+                ..extraction
+            }
+            .ey(),
+            None
+        );
+        assert_eq!(
+            Extraction {
+                dose: 0.,
+                ..extraction
+            }
+            .ratio(),
+            None
+        );
+        assert_eq!(
+            Extraction {
+                beverage: f32::NAN,
+                ..extraction
+            }
+            .ey(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut data = shot1();
+        let extraction = Extraction {
+            dose: 18.0,
+            beverage: 36.0,
+            tds: 10.0,
+        };
+        extraction.apply(&mut data.meta);
+        assert_eq!(
+            (
+                data.meta.dose,
+                data.meta.beverage,
+                data.meta.tds,
+                data.meta.ey
+            ),
+            (18.0, 36.0, 10.0, 20.0)
+        );
+    }
+
+    #[test]
+    fn test_apply_keeps_the_samples() {
+        let mut data = shot1();
+        // Gaps must not count as edits.
+        data.pressure.pressure[3] = f32::NAN;
+        let mut edited = data.clone();
+        Extraction {
+            dose: 18.0,
+            beverage: 36.0,
+            tds: 10.0,
+        }
+        .apply(&mut edited.meta);
+        assert!(data.same_samples(&edited));
+
+        edited.elapsed.pop();
+        assert!(!data.same_samples(&edited));
+    }
+
+    #[test]
+    fn test_apply_json_round_trips() {
+        let mut raw: Value = serde_json::from_str(sample("shot1").unwrap()).unwrap();
+        let extraction = Extraction {
+            dose: 19.0,
+            beverage: 39.1,
+            tds: 9.5,
+        };
+        extraction.apply_json(&mut raw).unwrap();
+        assert_eq!(raw["meta"]["in"], "19.0");
+        assert_eq!(raw["meta"]["shot"]["tds"], "9.50");
+        assert_eq!(raw["meta"]["shot"]["ey"], "19.55");
+        // Other fields of the section are kept.
+        assert_eq!(raw["meta"]["shot"]["enjoyment"], "0");
+
+        let data: ShotData = serde_json::from_value::<ShotDataJson>(raw).unwrap().into();
+        assert_eq!(Extraction::of(&data), extraction);
+        assert_eq!(data.meta.ey, 19.55);
+    }
+
+    #[test]
+    fn test_apply_json_rejects_other_files() {
+        let mut value = serde_json::json!({ "version": 1 });
+        assert_eq!(
+            Extraction::default().apply_json(&mut value),
+            Err("not a shot file: missing meta".into())
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let target = BrewTarget::espresso();
+        assert_eq!(target.describe(10.0, 20.0), "balanced");
+        assert_eq!(target.describe(12.0, 18.0), "balanced");
+        assert_eq!(target.describe(7.0, 20.0), "weak");
+        assert_eq!(target.describe(10.0, 23.0), "over-extracted");
+        assert_eq!(target.describe(13.0, 16.0), "strong and under-extracted");
+    }
+}
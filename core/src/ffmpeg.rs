@@ -0,0 +1,256 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Range, Trim};
+
+/// Overlay produced by one of the exporters, to composite over the footage.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum OverlaySource {
+    /// Transparent PNG frames as written by `dvo-render`, starting at the first sample.
+    Frames { pattern: String, fps: f32 },
+    /// ASS script, timed against the untrimmed video.
+    Ass { path: String },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EncodingPreset {
+    #[default]
+    Web,
+    HighQuality,
+    Small,
+}
+
+impl EncodingPreset {
+    pub const ALL: [EncodingPreset; 3] = [
+        EncodingPreset::Web,
+        EncodingPreset::HighQuality,
+        EncodingPreset::Small,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Web => "Web (H.264)",
+            Self::HighQuality => "High quality",
+            Self::Small => "Small file",
+        }
+    }
+
+    fn args(&self) -> &'static str {
+        match self {
+            Self::Web => {
+                "-c:v libx264 -preset medium -crf 20 -pix_fmt yuv420p -c:a aac -b:a 160k -movflags +faststart"
+            }
+            Self::HighQuality => {
+                "-c:v libx264 -preset slow -crf 16 -pix_fmt yuv420p -c:a aac -b:a 256k"
+            }
+            Self::Small => {
+                "-c:v libx264 -preset veryfast -crf 28 -pix_fmt yuv420p -c:a aac -b:a 96k -movflags +faststart"
+            }
+        }
+    }
+}
+
+/// Everything needed to composite an exported overlay over the footage with ffmpeg.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FfmpegJob {
+    pub video: String,
+    pub output: String,
+    pub source: OverlaySource,
+    /// Shot time covered by the overlay.
+    pub time_span: Range,
+    /// Seconds into the video at which the shot starts.
+    pub offset: f32,
+    pub trim: Trim,
+    /// Size of the output video; the overlay is scaled to match.
+    pub size: (u32, u32),
+    pub preset: EncodingPreset,
+}
+
+/// Quotes `arg` for a POSIX shell when it holds anything but safe characters.
+fn quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./:=+%@,".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        arg.into()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+/// Escapes a value inside a filter graph, e.g. a file name given to `ass=`.
+fn escape_filter(value: &str) -> String {
+    let mut escaped = String::default();
+    for c in value.chars() {
+        if r"\':,;[]".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl FfmpegJob {
+    /// Start and length of the trimmed footage, in seconds of the video.
+    fn cut(&self) -> (f32, f32) {
+        let start = (self.time_span.min + self.offset - self.trim.pre_roll).max(0.);
+        let end = self.time_span.max + self.offset + self.trim.post_roll;
+        (start, (end - start).max(0.))
+    }
+
+    fn filter_graph(&self) -> String {
+        let (width, height) = self.size;
+        let (start, _) = self.cut();
+        match &self.source {
+            OverlaySource::Frames { .. } => {
+                let delay = self.time_span.min + self.offset - start;
+                format!(
+                    "[0:v]scale={w}:{h}[base];\
+                     [1:v]setpts=PTS-STARTPTS+{delay:.3}/TB,scale={w}:{h}[ov];\
+                     [base][ov]overlay=0:0:eof_action=pass[out]",
+                    w = width,
+                    h = height,
+                    delay = delay
+                )
+            }
+            // Put the trimmed footage back on the video's clock while the script is burnt in.
+            OverlaySource::Ass { path } => format!(
+                "[0:v]setpts=PTS-STARTPTS+{start:.3}/TB,ass={path},setpts=PTS-STARTPTS,scale={w}:{h}[out]",
+                start = start,
+                path = escape_filter(path),
+                w = width,
+                h = height
+            ),
+        }
+    }
+
+    /// Arguments grouped the way they read best, one group per line of the script.
+    fn arg_groups(&self) -> Vec<Vec<String>> {
+        let (start, duration) = self.cut();
+        let mut groups = vec![vec![
+            "-ss".into(),
+            format!("{:.3}", start),
+            "-t".into(),
+            format!("{:.3}", duration),
+            "-i".into(),
+            self.video.clone(),
+        ]];
+        if let OverlaySource::Frames { pattern, fps } = &self.source {
+            groups.push(vec![
+                "-framerate".into(),
+                format!("{}", fps),
+                "-i".into(),
+                pattern.clone(),
+            ]);
+        }
+        groups.push(vec!["-filter_complex".into(), self.filter_graph()]);
+        groups.push(vec![
+            "-map".into(),
+            "[out]".into(),
+            "-map".into(),
+            "0:a?".into(),
+        ]);
+        groups.push(self.preset.args().split(' ').map(String::from).collect());
+        groups.push(vec![self.output.clone()]);
+        groups
+    }
+
+    /// Single-line command to paste into a terminal.
+    pub fn command(&self) -> String {
+        let args: Vec<String> = self
+            .arg_groups()
+            .iter()
+            .flatten()
+            .map(|arg| quote(arg))
+            .collect();
+        format!("ffmpeg {}", args.join(" "))
+    }
+
+    /// Same command as a POSIX shell script.
+    pub fn script(&self) -> String {
+        let lines: Vec<String> = self
+            .arg_groups()
+            .iter()
+            .map(|group| {
+                let args: Vec<String> = group.iter().map(|arg| quote(arg)).collect();
+                format!("  {}", args.join(" "))
+            })
+            .collect();
+        format!("#!/bin/sh\nset -e\n\nffmpeg \\\n{}\n", lines.join(" \\\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(source: OverlaySource) -> FfmpegJob {
+        FfmpegJob {
+            video: "my shot.mp4".into(),
+            output: "out.mp4".into(),
+            source,
+            time_span: Range {
+                min: 0.0,
+                max: 30.0,
+            },
+            offset: 3.0,
+            trim: Trim {
+                pre_roll: 2.0,
+                post_roll: 1.5,
+            },
+            size: (1080, 1920),
+            preset: EncodingPreset::Small,
+        }
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("frames/frame_%05d.png"), "frames/frame_%05d.png");
+        assert_eq!(quote("it's.mp4"), r"'it'\''s.mp4'");
+        assert_eq!(quote("[out]"), "'[out]'");
+    }
+
+    #[test]
+    fn test_escape_filter() {
+        assert_eq!(escape_filter(r"C:\a,b.ass"), r"C\:\\a\,b.ass");
+    }
+
+    #[test]
+    fn test_command_with_frames() {
+        let job = job(OverlaySource::Frames {
+            pattern: "frames/frame_%05d.png".into(),
+            fps: 30.,
+        });
+        assert_eq!(
+            job.command(),
+            "ffmpeg -ss 1.000 -t 33.500 -i 'my shot.mp4' \
+             -framerate 30 -i frames/frame_%05d.png \
+             -filter_complex '[0:v]scale=1080:1920[base];[1:v]setpts=PTS-STARTPTS+2.000/TB,scale=1080:1920[ov];[base][ov]overlay=0:0:eof_action=pass[out]' \
+             -map '[out]' -map '0:a?' \
+             -c:v libx264 -preset veryfast -crf 28 -pix_fmt yuv420p -c:a aac -b:a 96k -movflags +faststart \
+             out.mp4"
+        );
+    }
+
+    #[test]
+    fn test_command_with_ass_clamps_pre_roll() {
+        let mut job = job(OverlaySource::Ass {
+            path: "overlay.ass".into(),
+        });
+        job.offset = 0.5;
+        let command = job.command();
+        assert!(command.starts_with("ffmpeg -ss 0.000 -t 32.000 -i 'my shot.mp4' -filter_complex"));
+        assert!(command.contains(
+            "'[0:v]setpts=PTS-STARTPTS+0.000/TB,ass=overlay.ass,setpts=PTS-STARTPTS,scale=1080:1920[out]'"
+        ));
+    }
+
+    #[test]
+    fn test_script() {
+        let job = job(OverlaySource::Ass {
+            path: "overlay.ass".into(),
+        });
+        let script = job.script();
+        assert!(script.starts_with(
+            "#!/bin/sh\nset -e\n\nffmpeg \\\n  -ss 1.000 -t 33.500 -i 'my shot.mp4' \\\n"
+        ));
+        assert!(script.ends_with("  out.mp4\n"));
+    }
+}
@@ -0,0 +1,197 @@
+use std::rc::Rc;
+
+use crate::{
+    models::{ChartData, SeriesKind},
+    Rect, Units,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HudLine {
+    pub kind: SeriesKind,
+    pub text: String,
+    pub position: (f32, f32),
+}
+
+/// Readout of the current value of each series.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Hud {
+    pub lines: Vec<HudLine>,
+    /// Vertical space taken from the top of the area.
+    pub height: f32,
+}
+
+/// Rough width of one readout, in multiples of the font size.
+const COLUMN_EMS: f32 = 9.0;
+/// Extra width of a readout followed by its difference from the reference.
+const DELTA_EMS: f32 = 4.0;
+const LINE_HEIGHT: f32 = 1.4;
+
+impl Hud {
+    /// Lays the latest values at `t` seconds out along the top of `area`,
+    /// wrapping onto more rows when the area is too narrow. Each value is
+    /// followed by how far it is from the same series of `reference`, if any.
+    /// `charts` are expected to be converted into `units` already.
+    pub fn build(
+        charts: &[(SeriesKind, Rc<ChartData>)],
+        reference: &[(SeriesKind, Rc<ChartData>)],
+        units: &Units,
+        t: f32,
+        area: &Rect,
+        font_size: f32,
+    ) -> Self {
+        let ems = if reference.is_empty() {
+            COLUMN_EMS
+        } else {
+            COLUMN_EMS + DELTA_EMS
+        };
+        let columns =
+            ((area.width / (font_size * ems)).floor() as usize).clamp(1, charts.len().max(1));
+        let rows = charts.len().div_ceil(columns);
+        let column = area.width / columns as f32;
+        let line_height = font_size * LINE_HEIGHT;
+        let lines = charts
+            .iter()
+            .enumerate()
+            .map(|(i, (kind, data))| {
+                let value = data.value_at(t);
+                let delta = reference
+                    .iter()
+                    .find(|(other, _)| other == kind)
+                    .and_then(|(_, reference)| reference.value_at(t))
+                    .zip(value)
+                    .map(|(reference, value)| format!(" ({:+.1})", value - reference))
+                    .unwrap_or_default();
+                let value = match value {
+                    Some(v) => format!("{:.1}", v),
+                    None => "-".into(),
+                };
+                HudLine {
+                    kind: *kind,
+                    text: format!(
+                        "{} {} {}{}",
+                        kind.label(),
+                        value,
+                        units.symbol(kind.quantity()),
+                        delta
+                    ),
+                    position: (
+                        area.x + column * (i % columns) as f32,
+                        area.y + font_size + line_height * (i / columns) as f32,
+                    ),
+                }
+            })
+            .collect();
+        Self {
+            lines,
+            height: line_height * rows as f32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{models::DataPoint, Range};
+
+    #[test]
+    fn test_hud_build() {
+        let pressure = ChartData {
+            series: vec![
+                DataPoint::Present((0.0, 1.0)),
+                DataPoint::Present((1.0, 6.04)),
+            ],
+            range: Range {
+                min: 1.0,
+                max: 6.04,
+            },
+        };
+        let flow = ChartData {
+            series: vec![DataPoint::Present((2.0, 1.0))],
+            range: Range { min: 1.0, max: 1.0 },
+        };
+        let charts = vec![
+            (SeriesKind::Pressure, Rc::new(pressure)),
+            (SeriesKind::Flow, Rc::new(flow)),
+        ];
+        let actual = Hud::build(
+            &charts,
+            &[],
+            &Units::METRIC,
+            1.5,
+            &Rect::new(10., 20., 240., 100.),
+            12.,
+        );
+        let expected = Hud {
+            lines: vec![
+                HudLine {
+                    kind: SeriesKind::Pressure,
+                    text: "Pressure 6.0 bar".into(),
+                    position: (10., 32.),
+                },
+                HudLine {
+                    kind: SeriesKind::Flow,
+                    text: "Flow - ml/s".into(),
+                    position: (130., 32.),
+                },
+            ],
+            height: 12. * LINE_HEIGHT,
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_hud_build_wraps() {
+        let charts: Vec<_> = SeriesKind::ALL
+            .iter()
+            .map(|kind| {
+                let data = ChartData {
+                    series: vec![],
+                    range: Range { min: 0.0, max: 0.0 },
+                };
+                (*kind, Rc::new(data))
+            })
+            .collect();
+        let actual = Hud::build(
+            &charts,
+            &[],
+            &Units::METRIC,
+            0.0,
+            &Rect::new(0., 0., 240., 100.),
+            10.,
+        );
+        let positions: Vec<_> = actual.lines.iter().map(|line| line.position).collect();
+        assert_eq!(
+            positions,
+            vec![(0., 10.), (120., 10.), (0., 24.), (120., 24.), (0., 38.)]
+        );
+        assert_eq!(actual.height, 42.);
+    }
+
+    #[test]
+    fn test_hud_build_with_reference() {
+        let series = |v: f32| {
+            Rc::new(ChartData {
+                series: vec![DataPoint::Present((0.0, v))],
+                range: Range { min: v, max: v },
+            })
+        };
+        let charts = vec![
+            (SeriesKind::Pressure, series(6.04)),
+            (SeriesKind::Flow, series(2.0)),
+        ];
+        let reference = vec![(SeriesKind::Pressure, series(6.5))];
+        let actual = Hud::build(
+            &charts,
+            &reference,
+            &Units::METRIC,
+            1.0,
+            &Rect::new(0., 0., 240., 100.),
+            10.,
+        );
+        let texts: Vec<_> = actual.lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts, ["Pressure 6.0 bar (-0.5)", "Flow 2.0 ml/s"]);
+        // The readouts are wider, so they no longer fit side by side.
+        assert_eq!(actual.lines[1].position, (0., 24.));
+    }
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Box actually covered by a media of `intrinsic` size inside `container`
+    /// with `object-fit: contain`, i.e. excluding the letterbox bars.
+    pub fn contain(intrinsic: (f32, f32), container: (f32, f32)) -> Self {
+        let (iw, ih) = intrinsic;
+        let (cw, ch) = container;
+        if iw <= 0. || ih <= 0. || cw <= 0. || ch <= 0. {
+            return Self::new(0., 0., cw.max(0.), ch.max(0.));
+        }
+
+        let ratio = (cw / iw).min(ch / ih);
+        let (width, height) = (iw * ratio, ih * ratio);
+        Self::new((cw - width) / 2., (ch - height) / 2., width, height)
+    }
+
+    pub fn size(&self) -> (f32, f32) {
+        (self.width, self.height)
+    }
+
+    /// Turns a rect relative to a frame (0..1 on both axes) into pixels of a frame of `size`.
+    pub fn scale(&self, size: (f32, f32)) -> Self {
+        let (width, height) = size;
+        Self::new(
+            self.x * width,
+            self.y * height,
+            self.width * width,
+            self.height * height,
+        )
+    }
+
+    pub fn inflate(&self, d: f32) -> Self {
+        Self::new(
+            self.x - d,
+            self.y - d,
+            self.width + d * 2.,
+            self.height + d * 2.,
+        )
+    }
+}
+
+const MARGIN: f32 = 0.03;
+const CORNER: (f32, f32) = (0.45, 0.3);
+
+/// Where the chart block sits on the video frame.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Placement {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    #[default]
+    FullWidth,
+    /// Rect relative to the frame (0..1 on both axes).
+    Custom(Rect),
+}
+
+impl Placement {
+    pub const PRESETS: [Placement; 5] = [
+        Placement::TopLeft,
+        Placement::TopRight,
+        Placement::BottomLeft,
+        Placement::BottomRight,
+        Placement::FullWidth,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::TopLeft => "Top left",
+            Self::TopRight => "Top right",
+            Self::BottomLeft => "Bottom left",
+            Self::BottomRight => "Bottom right",
+            Self::FullWidth => "Full width",
+            Self::Custom(_) => "Custom",
+        }
+    }
+
+    /// Chart block relative to the frame (0..1 on both axes).
+    pub fn rect(&self) -> Rect {
+        let (w, h) = CORNER;
+        match self {
+            Self::TopLeft => Rect::new(MARGIN, MARGIN, w, h),
+            Self::TopRight => Rect::new(1. - MARGIN - w, MARGIN, w, h),
+            Self::BottomLeft => Rect::new(MARGIN, 1. - MARGIN - h, w, h),
+            Self::BottomRight => Rect::new(1. - MARGIN - w, 1. - MARGIN - h, w, h),
+            Self::FullWidth => Rect::new(MARGIN, 0.45, 1. - MARGIN * 2., 0.52),
+            Self::Custom(rect) => rect.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect_contain_pillarbox() {
+        let actual = Rect::contain((1080., 1920.), (1000., 800.));
+        let expected = Rect::new(275., 0., 450., 800.);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_rect_contain_letterbox() {
+        let actual = Rect::contain((3840., 2160.), (800., 800.));
+        let expected = Rect::new(0., 175., 800., 450.);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_rect_scale() {
+        let actual = Rect::new(0.25, 0.5, 0.5, 0.25).scale((800., 400.));
+        let expected = Rect::new(200., 200., 400., 100.);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_placement_rect() {
+        let actual = Placement::BottomRight.rect().scale((1000., 1000.));
+        assert!((actual.x + actual.width - 970.).abs() < 1e-3);
+        assert!((actual.y + actual.height - 970.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_rect_contain_unknown_intrinsic() {
+        let actual = Rect::contain((0., 0.), (640., 360.));
+        let expected = Rect::new(0., 0., 640., 360.);
+        assert_eq!(actual, expected);
+    }
+}
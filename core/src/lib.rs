@@ -0,0 +1,50 @@
+mod animation;
+mod archive;
+mod ass;
+mod axis;
+mod compare;
+mod extraction;
+mod ffmpeg;
+mod hud;
+mod layout;
+mod library;
+pub mod models;
+mod overlay;
+mod path;
+mod project;
+mod range;
+mod scale;
+mod settings;
+mod shot_csv;
+pub mod stats;
+mod subtitles;
+mod theme;
+mod units;
+mod validate;
+mod view_state;
+
+pub use animation::{frame_count, AnimationEncoder, AnimationFormat};
+pub use archive::{read_archive, ArchiveEntry};
+pub use ass::to_ass;
+pub use axis::{Axis, TICK_LENGTH};
+pub use compare::{Alignment, Comparison};
+pub use extraction::{BrewTarget, Extraction};
+pub use ffmpeg::{EncodingPreset, FfmpegJob, OverlaySource};
+pub use hud::Hud;
+pub use layout::{Placement, Rect};
+pub use library::{sort_shots, LibraryShot, SortKey};
+pub use overlay::{Overlay, Stroke, AXIS_LABEL_SIZE, HUD_FONT_SIZE};
+pub use path::Path;
+pub use project::{
+    fingerprint, Annotation, Project, ProjectError, ShotRef, VideoRef, FINGERPRINT_CHUNK,
+    PROJECT_EXTENSION, PROJECT_VERSION,
+};
+pub use range::Range;
+pub use scale::scale;
+pub use settings::{Backend, Settings, Trim};
+pub use shot_csv::{read_csv, to_csv, Column, CsvLayout, CsvTable, TimeUnit};
+pub use subtitles::{cues, Cue, SubtitleFormat};
+pub use theme::{Theme, ThemePreset};
+pub use units::{PressureUnit, Quantity, TemperatureUnit, Units, VolumeUnit, WeightUnit};
+pub use validate::{validate, Diagnostic, Severity};
+pub use view_state::ViewState;
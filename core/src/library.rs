@@ -0,0 +1,224 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fingerprint,
+    models::{ShotData, ShotFormat},
+};
+
+/// A shot imported into the local library.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LibraryShot {
+    pub id: String,
+    /// Name of the file it was imported from.
+    pub file_name: String,
+    pub data: ShotData,
+}
+
+impl LibraryShot {
+    /// Shots are keyed by the time they were pulled, so importing the same shot
+    /// twice replaces it instead of adding a copy. Shots without that time, e.g.
+    /// from a CSV file, are keyed by their file name and contents.
+    pub fn new(file_name: &str, data: ShotData) -> Self {
+        let id = if 0 < data.timestamp {
+            data.timestamp.to_string()
+        } else {
+            let contents = serde_json::to_vec(&data).expect("shot is always serialisable");
+            fingerprint(contents.len() as u64, file_name.as_bytes(), &contents)
+        };
+        Self {
+            id,
+            file_name: file_name.into(),
+            data,
+        }
+    }
+
+    /// Parses the contents of a shot file of any [`ShotFormat`].
+    pub fn parse(file_name: &str, text: &str) -> Result<Self, String> {
+        let format = ShotFormat::detect(text).ok_or("not a shot file of a known format")?;
+        let data = format
+            .parse(text)
+            .map_err(|e| format!("{}: {}", format.label(), e))?;
+        if data.elapsed.is_empty() {
+            return Err(format!("{}: the shot has no samples", format.label()));
+        }
+        Ok(Self::new(file_name, data))
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.data.elapsed.last().copied().unwrap_or_default()
+    }
+
+    pub fn bean(&self) -> String {
+        let meta = &self.data.meta;
+        format!("{} {}", meta.bean_brand, meta.bean_type)
+            .trim()
+            .to_string()
+    }
+
+    /// Case-insensitive search over the profile, bean, date and file name.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        [
+            self.data.profile.title.as_str(),
+            self.bean().as_str(),
+            self.data.date.as_str(),
+            self.file_name.as_str(),
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortKey {
+    #[default]
+    Date,
+    Profile,
+    Bean,
+    Dose,
+    Yield,
+    Duration,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 6] = [
+        SortKey::Date,
+        SortKey::Profile,
+        SortKey::Bean,
+        SortKey::Dose,
+        SortKey::Yield,
+        SortKey::Duration,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Date => "Date",
+            Self::Profile => "Profile",
+            Self::Bean => "Bean",
+            Self::Dose => "Dose",
+            Self::Yield => "Yield",
+            Self::Duration => "Duration",
+        }
+    }
+
+    pub fn compare(&self, a: &LibraryShot, b: &LibraryShot) -> Ordering {
+        let (a_meta, b_meta) = (&a.data.meta, &b.data.meta);
+        match self {
+            Self::Date => a.data.timestamp.cmp(&b.data.timestamp),
+            Self::Profile => a.data.profile.title.cmp(&b.data.profile.title),
+            Self::Bean => a.bean().cmp(&b.bean()),
+            Self::Dose => a_meta.dose.total_cmp(&b_meta.dose),
+            Self::Yield => a_meta.beverage.total_cmp(&b_meta.beverage),
+            Self::Duration => a.duration().total_cmp(&b.duration()),
+        }
+    }
+}
+
+/// Sorts by `key`, falling back to the date so that ties keep a stable order.
+pub fn sort_shots(shots: &mut [LibraryShot], key: SortKey, descending: bool) {
+    shots.sort_by(|a, b| {
+        let ordering = key.compare(a, b).then_with(|| SortKey::Date.compare(a, b));
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::{json::SHOT2, MetaSection, ProfileSection};
+
+    fn shot(timestamp: usize, title: &str, dose: f32, duration: f32) -> LibraryShot {
+        let data = ShotData {
+            timestamp,
+            elapsed: vec![0.0, duration],
+            profile: ProfileSection {
+                title: title.into(),
+                steps: vec![],
+            },
+            meta: MetaSection {
+                bean_brand: "Wakaki".into(),
+                bean_type: "Worka".into(),
+                dose,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        LibraryShot::new("shot.json", data)
+    }
+
+    #[test]
+    fn test_library_shot_id() {
+        assert_eq!(shot(1685582363, "", 0.0, 0.0).id, "1685582363");
+        let a = LibraryShot::new("a.json", ShotData::default());
+        let b = LibraryShot::new("b.json", ShotData::default());
+        assert_ne!(a.id, b.id);
+
+        // Different shots exported under the same name are kept apart.
+        let other = ShotData {
+            elapsed: vec![0.0, 1.0],
+            ..Default::default()
+        };
+        let c = LibraryShot::new("a.json", other.clone());
+        assert_ne!(a.id, c.id);
+        assert_eq!(LibraryShot::new("a.json", other).id, c.id);
+    }
+
+    #[test]
+    fn test_library_shot_parse() {
+        let shot = LibraryShot::parse("shot2.json", SHOT2).unwrap();
+        assert_eq!(shot.id, "1683084174");
+        assert_eq!(shot.bean(), "Wakaki Coffee Ethiopia Worka Sakaro Natural");
+        assert!(LibraryShot::parse("notes.json", "{}").is_err());
+
+        let legacy = include_str!("../../data/legacy/advanced.shot");
+        let shot = LibraryShot::parse("20200514T134113.shot", legacy).unwrap();
+        assert_eq!(shot.id, "1589456473");
+        assert_eq!(shot.bean(), "Square Mile Red Brick");
+        assert!(LibraryShot::parse("notes.shot", "{}").is_err());
+
+        let visualizer = include_str!("../../data/visualizer/shot.json");
+        let shot = LibraryShot::parse("download.json", visualizer).unwrap();
+        assert_eq!(shot.id, "1683084174");
+        assert_eq!(
+            LibraryShot::parse("broken.shot", "espresso_elapsed {0.0").unwrap_err(),
+            "DE1 .shot: unmatched open brace in list"
+        );
+    }
+
+    #[test]
+    fn test_library_shot_matches() {
+        let shot = shot(1, "Far East Cliff", 18.0, 30.0);
+        assert!(shot.matches("far east"));
+        assert!(shot.matches(" WORKA "));
+        assert!(shot.matches("shot.json"));
+        assert!(!shot.matches("blooming"));
+    }
+
+    #[test]
+    fn test_sort_shots() {
+        let mut shots = vec![
+            shot(2, "B", 18.0, 40.0),
+            shot(1, "A", 20.0, 30.0),
+            shot(3, "C", 18.0, 35.0),
+        ];
+        let ids = |shots: &[LibraryShot]| -> Vec<String> {
+            shots.iter().map(|shot| shot.id.clone()).collect()
+        };
+
+        sort_shots(&mut shots, SortKey::Date, true);
+        assert_eq!(ids(&shots), ["3", "2", "1"]);
+        sort_shots(&mut shots, SortKey::Dose, false);
+        assert_eq!(ids(&shots), ["2", "3", "1"]);
+        sort_shots(&mut shots, SortKey::Duration, false);
+        assert_eq!(ids(&shots), ["1", "3", "2"]);
+        sort_shots(&mut shots, SortKey::Profile, true);
+        assert_eq!(ids(&shots), ["3", "2", "1"]);
+    }
+}
@@ -0,0 +1,306 @@
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{models::ShotData, Quantity, Range, Units};
+
+#[derive(Debug, PartialEq)]
+pub struct ChartData {
+    pub series: Vec<DataPoint>,
+    pub range: Range,
+}
+
+impl ChartData {
+    pub fn of(kind: SeriesKind, data: &ShotData) -> Self {
+        match kind {
+            SeriesKind::Pressure => Self::pressure(data),
+            SeriesKind::Flow => Self::flow(data),
+            SeriesKind::FlowByWeight => Self::flow_by_weight(data),
+            SeriesKind::TempBasket => Self::temp_basket(data),
+            SeriesKind::TempMix => Self::temp_mix(data),
+        }
+    }
+
+    /// Same as [`ChartData::of`] with values converted into `units`.
+    pub fn of_in(kind: SeriesKind, data: &ShotData, units: &Units) -> Self {
+        Self::of(kind, data).map(|v| units.convert(kind.quantity(), v))
+    }
+
+    /// Every drawable series of `data`, converted into `units`.
+    pub fn all_in(data: &ShotData, units: &Units) -> Vec<(SeriesKind, Rc<Self>)> {
+        SeriesKind::ALL
+            .iter()
+            .map(|kind| (*kind, Rc::new(Self::of_in(*kind, data, units))))
+            .collect()
+    }
+
+    /// Applies a monotonically increasing conversion to every value.
+    pub fn map(self, f: impl Fn(f32) -> f32) -> Self {
+        let series = self
+            .series
+            .into_iter()
+            .map(|dp| match dp {
+                DataPoint::Present((t, v)) => DataPoint::Present((t, f(v))),
+                dp => dp,
+            })
+            .collect();
+        Self {
+            series,
+            range: Range {
+                min: f(self.range.min),
+                max: f(self.range.max),
+            },
+        }
+    }
+
+    /// Moves every sample `dt` seconds later, dropping those that end up outside `span`.
+    pub fn shift(self, dt: f32, span: &Range) -> Self {
+        let series = self
+            .series
+            .into_iter()
+            .map(|dp| match dp {
+                DataPoint::Present((t, v)) => DataPoint::Present((t + dt, v)),
+                DataPoint::NoData(t) => DataPoint::NoData(t + dt),
+            })
+            .filter(|dp| span.min <= dp.t() && dp.t() <= span.max)
+            .collect();
+        Self {
+            series,
+            range: self.range,
+        }
+    }
+
+    /// Latest recorded value at `t` seconds.
+    pub fn value_at(&self, t: f32) -> Option<f32> {
+        self.series
+            .iter()
+            .take_while(|dp| dp.t() <= t)
+            .filter_map(|dp| match dp {
+                DataPoint::Present((_, v)) => Some(*v),
+                DataPoint::NoData(_) => None,
+            })
+            .last()
+    }
+
+    pub fn pressure(data: &ShotData) -> Self {
+        Self::from_series(&data.elapsed, &data.pressure.pressure)
+    }
+
+    pub fn temp_basket(data: &ShotData) -> Self {
+        Self::from_series(&data.elapsed, &data.temperature.basket)
+    }
+
+    pub fn temp_mix(data: &ShotData) -> Self {
+        Self::from_series(&data.elapsed, &data.temperature.mix)
+    }
+
+    pub fn flow(data: &ShotData) -> Self {
+        Self::from_series(&data.elapsed, &data.flow.flow)
+    }
+
+    pub fn flow_by_weight(data: &ShotData) -> Self {
+        Self::from_series(&data.elapsed, &data.flow.by_weight)
+    }
+
+    /// Weight in the cup, which has no line of its own on the overlay.
+    pub fn weight(data: &ShotData) -> Self {
+        Self::from_series(&data.elapsed, &data.totals.weight)
+    }
+
+    /// One point per time of `elapsed`. Samples a short series lacks and
+    /// values that aren't numbers are gaps; see [`crate::validate`] for the report.
+    fn from_series(elapsed: &[f32], values: &[f32]) -> Self {
+        let series: Vec<DataPoint> = elapsed
+            .iter()
+            .enumerate()
+            .map(|(i, t)| match values.get(i) {
+                Some(v) if v.is_finite() => DataPoint::Present((*t, *v)),
+                _ => DataPoint::NoData(*t),
+            })
+            .collect();
+        let present: Vec<f32> = series
+            .iter()
+            .filter_map(|dp| match dp {
+                DataPoint::Present((_, v)) => Some(*v),
+                DataPoint::NoData(_) => None,
+            })
+            .collect();
+        let range = if present.is_empty() {
+            Range { min: 0., max: 0. }
+        } else {
+            Range::from_series(&present)
+        };
+        Self { series, range }
+    }
+}
+
+/// Series of a shot that can be drawn on the overlay.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SeriesKind {
+    Pressure,
+    Flow,
+    FlowByWeight,
+    TempBasket,
+    TempMix,
+}
+
+impl SeriesKind {
+    pub const ALL: [SeriesKind; 5] = [
+        SeriesKind::Pressure,
+        SeriesKind::Flow,
+        SeriesKind::FlowByWeight,
+        SeriesKind::TempBasket,
+        SeriesKind::TempMix,
+    ];
+
+    pub fn quantity(&self) -> Quantity {
+        match self {
+            Self::Pressure => Quantity::Pressure,
+            Self::Flow => Quantity::Flow,
+            Self::FlowByWeight => Quantity::FlowByWeight,
+            Self::TempBasket | Self::TempMix => Quantity::Temperature,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Pressure => "Pressure",
+            Self::Flow => "Flow",
+            Self::FlowByWeight => "Weight flow",
+            Self::TempBasket => "Basket",
+            Self::TempMix => "Mix",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DataPoint {
+    NoData(f32),
+    Present((f32, f32)),
+}
+
+impl DataPoint {
+    pub fn t(&self) -> f32 {
+        match self {
+            Self::NoData(t) => *t,
+            Self::Present((t, _)) => *t,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::{PressureSection, TemperatureSection};
+
+    #[test]
+    fn test_chart_data_for_pressure() {
+        let data = ShotData {
+            elapsed: vec![0.0, 0.044, 0.268],
+            pressure: PressureSection {
+                pressure: vec![0.0, 0.03, 0.22],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let actual = ChartData::pressure(&data);
+        let expected = ChartData {
+            series: vec![
+                DataPoint::Present((0.0, 0.0)),
+                DataPoint::Present((0.044, 0.03)),
+                DataPoint::Present((0.268, 0.22)),
+            ],
+            range: Range {
+                min: 0.0,
+                max: 0.22,
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_chart_data_in_units() {
+        let data = ShotData {
+            elapsed: vec![0.0, 1.0],
+            temperature: TemperatureSection {
+                basket: vec![0.0, 100.0],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let actual = ChartData::of_in(SeriesKind::TempBasket, &data, &Units::IMPERIAL);
+        let expected = ChartData {
+            series: vec![
+                DataPoint::Present((0.0, 32.0)),
+                DataPoint::Present((1.0, 212.0)),
+            ],
+            range: Range {
+                min: 32.0,
+                max: 212.0,
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_chart_data_with_gaps() {
+        let data = ShotData {
+            elapsed: vec![0.0, 1.0, 2.0, 3.0],
+            pressure: PressureSection {
+                pressure: vec![1.0, f32::NAN, 3.0],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let actual = ChartData::pressure(&data);
+        let expected = ChartData {
+            series: vec![
+                DataPoint::Present((0.0, 1.0)),
+                DataPoint::NoData(1.0),
+                DataPoint::Present((2.0, 3.0)),
+                DataPoint::NoData(3.0),
+            ],
+            range: Range { min: 1.0, max: 3.0 },
+        };
+        assert_eq!(actual, expected);
+
+        let actual = ChartData::flow(&data);
+        assert_eq!(actual.series.len(), 4);
+        assert_eq!(actual.range, Range { min: 0.0, max: 0.0 });
+        assert_eq!(actual.value_at(3.0), None);
+    }
+
+    #[test]
+    fn test_chart_data_value_at() {
+        let data = ChartData {
+            series: vec![
+                DataPoint::Present((0.0, 1.0)),
+                DataPoint::Present((1.0, 2.0)),
+                DataPoint::NoData(2.0),
+            ],
+            range: Range { min: 1.0, max: 2.0 },
+        };
+        assert_eq!(data.value_at(-1.0), None);
+        assert_eq!(data.value_at(0.5), Some(1.0));
+        assert_eq!(data.value_at(5.0), Some(2.0));
+    }
+
+    #[test]
+    fn test_chart_data_shift() {
+        let data = ChartData {
+            series: vec![
+                DataPoint::Present((0.0, 1.0)),
+                DataPoint::NoData(1.0),
+                DataPoint::Present((2.0, 3.0)),
+            ],
+            range: Range { min: 1.0, max: 3.0 },
+        };
+        let actual = data.shift(-1.0, &Range { min: 0.0, max: 0.5 });
+        let expected = ChartData {
+            series: vec![DataPoint::NoData(0.0)],
+            range: Range { min: 1.0, max: 3.0 },
+        };
+        assert_eq!(actual, expected);
+    }
+}
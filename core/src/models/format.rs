@@ -0,0 +1,101 @@
+use serde_json::Value;
+
+use super::{json::ShotDataJson, tcl, visualizer::ShotDataVisualizer, ShotData};
+
+/// File formats shots can be imported from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShotFormat {
+    /// JSON history file of the DE1 app (v2).
+    De1Json,
+    /// Tcl history file of de1app versions before the JSON format.
+    De1Tcl,
+    /// JSON download from visualizer.coffee.
+    Visualizer,
+}
+
+impl ShotFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::De1Json => "DE1 JSON",
+            Self::De1Tcl => "DE1 .shot",
+            Self::Visualizer => "Visualizer",
+        }
+    }
+
+    /// Tells the format from the contents alone, as file names are often lost
+    /// or wrong by the time a shot is shared.
+    pub fn detect(text: &str) -> Option<Self> {
+        if !text.trim_start().starts_with('{') {
+            return text.contains("espresso_elapsed").then_some(Self::De1Tcl);
+        }
+        let Ok(Value::Object(object)) = serde_json::from_str::<Value>(text) else {
+            // A Tcl dict may open with a braced key too.
+            return text.contains("espresso_elapsed").then_some(Self::De1Tcl);
+        };
+        if object.contains_key("timeframe") && object.contains_key("data") {
+            Some(Self::Visualizer)
+        } else if object.contains_key("elapsed") && object.contains_key("pressure") {
+            Some(Self::De1Json)
+        } else {
+            None
+        }
+    }
+
+    pub fn parse(&self, text: &str) -> Result<ShotData, String> {
+        match self {
+            Self::De1Json => serde_json::from_str::<ShotDataJson>(text)
+                .map(ShotData::from)
+                .map_err(|e| e.to_string()),
+            Self::De1Tcl => tcl::parse_shot(text),
+            Self::Visualizer => serde_json::from_str::<ShotDataVisualizer>(text)
+                .map(ShotData::from)
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::json::{SHOT1, SHOT2};
+
+    #[test]
+    fn test_detect_fixtures() {
+        let fixtures = [
+            (SHOT1, ShotFormat::De1Json),
+            (SHOT2, ShotFormat::De1Json),
+            (
+                include_str!("../../../data/legacy/advanced.shot"),
+                ShotFormat::De1Tcl,
+            ),
+            (
+                include_str!("../../../data/legacy/simple.shot"),
+                ShotFormat::De1Tcl,
+            ),
+            (
+                include_str!("../../../data/visualizer/shot.json"),
+                ShotFormat::Visualizer,
+            ),
+            (
+                include_str!("../../../data/visualizer/minimal.json"),
+                ShotFormat::Visualizer,
+            ),
+        ];
+        for (text, format) in fixtures {
+            assert_eq!(ShotFormat::detect(text), Some(format));
+            let data = format.parse(text).unwrap();
+            assert!(!data.elapsed.is_empty(), "{}", format.label());
+            assert!(!data.pressure.pressure.is_empty(), "{}", format.label());
+        }
+    }
+
+    #[test]
+    fn test_detect_unknown() {
+        assert_eq!(ShotFormat::detect(""), None);
+        assert_eq!(ShotFormat::detect("{}"), None);
+        assert_eq!(ShotFormat::detect(r#"{ "version": 1 }"#), None);
+        assert_eq!(ShotFormat::detect("[1, 2, 3]"), None);
+        assert_eq!(ShotFormat::detect("clock 1576573513"), None);
+    }
+}
@@ -0,0 +1,16 @@
+mod shot_data;
+
+pub use shot_data::*;
+
+pub const SHOT1: &str = include_str!("../../../../data/shot1.json");
+pub const SHOT2: &str = include_str!("../../../../data/shot2.json");
+
+/// Shots bundled with the app, by id.
+pub const SAMPLES: [(&str, &str); 2] = [("shot1", SHOT1), ("shot2", SHOT2)];
+
+pub fn sample(id: &str) -> Option<&'static str> {
+    SAMPLES
+        .iter()
+        .find(|(sample_id, _)| *sample_id == id)
+        .map(|(_, json)| *json)
+}
@@ -1,53 +1,156 @@
 use serde::Deserialize;
 
+use crate::models::lenient;
+
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct ShotDataJson {
+    #[serde(deserialize_with = "lenient::text")]
     pub version: String,
+    #[serde(deserialize_with = "lenient::text")]
     pub clock: String,
     pub date: String,
+    #[serde(deserialize_with = "lenient::text")]
     pub timestamp: String,
+    #[serde(deserialize_with = "lenient::texts")]
     pub elapsed: Vec<String>,
     pub pressure: PressureSectionJson,
     pub flow: FlowSectionJson,
     pub temperature: TemperatureSectionJson,
     pub totals: TotalsSectionJson,
     pub resistance: ResistanceSectionJson,
+    #[serde(deserialize_with = "lenient::texts")]
     pub state_change: Vec<String>,
+    #[serde(default)]
+    pub profile: ProfileJson,
+    #[serde(default)]
+    pub meta: MetaJson,
+    #[serde(default)]
+    pub app: AppJson,
 }
 
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct PressureSectionJson {
+    #[serde(deserialize_with = "lenient::texts")]
     pub pressure: Vec<String>,
+    #[serde(deserialize_with = "lenient::texts")]
     pub goal: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct FlowSectionJson {
+    #[serde(deserialize_with = "lenient::texts")]
     pub flow: Vec<String>,
+    #[serde(deserialize_with = "lenient::texts")]
     pub by_weight: Vec<String>,
+    #[serde(deserialize_with = "lenient::texts")]
     pub by_weight_raw: Vec<String>,
+    #[serde(deserialize_with = "lenient::texts")]
     pub goal: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct TemperatureSectionJson {
+    #[serde(deserialize_with = "lenient::texts")]
     pub basket: Vec<String>,
+    #[serde(deserialize_with = "lenient::texts")]
     pub mix: Vec<String>,
+    #[serde(deserialize_with = "lenient::texts")]
     pub goal: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct TotalsSectionJson {
+    #[serde(deserialize_with = "lenient::texts")]
     pub weight: Vec<String>,
+    #[serde(deserialize_with = "lenient::texts")]
     pub water_dispensed: Vec<String>,
 }
 
 #[derive(Deserialize, Debug, PartialEq)]
 pub struct ResistanceSectionJson {
+    #[serde(deserialize_with = "lenient::texts")]
     pub resistance: Vec<String>,
+    #[serde(deserialize_with = "lenient::texts")]
     pub by_weight: Vec<String>,
 }
 
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct ProfileJson {
+    pub title: String,
+    pub steps: Vec<StepJson>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct StepJson {
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct MetaJson {
+    pub bean: BeanJson,
+    pub shot: ShotMetaJson,
+    pub grinder: GrinderJson,
+    #[serde(rename = "in", deserialize_with = "lenient::text")]
+    pub dose: String,
+    #[serde(rename = "out", deserialize_with = "lenient::text")]
+    pub beverage: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct BeanJson {
+    pub brand: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub roast_level: String,
+    pub roast_date: String,
+    pub notes: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct ShotMetaJson {
+    #[serde(deserialize_with = "lenient::text")]
+    pub enjoyment: String,
+    pub notes: String,
+    #[serde(deserialize_with = "lenient::text")]
+    pub tds: String,
+    #[serde(deserialize_with = "lenient::text")]
+    pub ey: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct GrinderJson {
+    pub model: String,
+    #[serde(deserialize_with = "lenient::text")]
+    pub setting: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct AppJson {
+    pub data: AppDataJson,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct AppDataJson {
+    pub settings: AppSettingsJson,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct AppSettingsJson {
+    #[serde(deserialize_with = "lenient::text")]
+    pub enable_fahrenheit: String,
+    #[serde(deserialize_with = "lenient::text")]
+    pub enable_fluid_ounces: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,7 +308,20 @@ mod tests {
                     resistance: vec!["0.0".into(), "0.0".into(), "0.0".into()],
                     by_weight: vec!["0.0".into(), "0.0".into(), "0.0".into()]
                 },
-                state_change: vec!["0.0".into(), "10000000.0".into(), "10000000.0".into()]
+                state_change: vec!["0.0".into(), "10000000.0".into(), "10000000.0".into()],
+                profile: ProfileJson {
+                    title: "TurboBloom 87c".into(),
+                    steps: vec![],
+                },
+                meta: MetaJson {
+                    bean: BeanJson {
+                        brand: "Wakaki Coffee".into(),
+                        kind: "Ethiopia Worka Sakaro Natural".into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                app: AppJson::default(),
             })
         );
     }
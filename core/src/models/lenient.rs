@@ -0,0 +1,160 @@
+//! Deserialisers for numeric fields that tools write either as JSON numbers or
+//! as strings, and sometimes as `null`. Used with `#[serde(deserialize_with)]`.
+
+use serde::{de::Error, Deserialize, Deserializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Scalar {
+    Number(f64),
+    Text(String),
+}
+
+impl Scalar {
+    fn into_text(self) -> String {
+        match self {
+            Self::Number(number) => number.to_string(),
+            Self::Text(text) => text,
+        }
+    }
+
+    fn into_number<E: Error>(self) -> Result<f64, E> {
+        match self {
+            Self::Number(number) => Ok(number),
+            Self::Text(text) => text
+                .trim()
+                .parse()
+                .ok()
+                .filter(|number: &f64| number.is_finite())
+                .ok_or_else(|| E::custom(format!("invalid number \"{}\"", text))),
+        }
+    }
+
+    /// Text as written, once checked to be a number.
+    fn into_number_text<E: Error>(self) -> Result<String, E> {
+        match self {
+            Self::Number(number) => Ok(number.to_string()),
+            Self::Text(text) => {
+                Self::Text(text.clone()).into_number::<E>()?;
+                Ok(text)
+            }
+        }
+    }
+}
+
+/// A value kept as text, e.g. a logged dose; `null` reads as empty.
+pub fn text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(Option::<Scalar>::deserialize(deserializer)?
+        .map(Scalar::into_text)
+        .unwrap_or_default())
+}
+
+/// Samples kept as text, each checked to be a number; `null` reads as no
+/// samples, and a `null` sample as an empty one.
+pub fn texts<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    numbers(deserializer, String::new(), Scalar::into_number_text)
+}
+
+/// Samples as numbers; `null` reads as no samples, and a `null` sample as NaN.
+pub fn floats<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<f32>, D::Error> {
+    numbers(deserializer, f32::NAN, |scalar| {
+        scalar.into_number::<D::Error>().map(|number| number as f32)
+    })
+}
+
+/// A logged number; `null` and empty strings read as not logged.
+pub fn optional_float<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f32>, D::Error> {
+    match Option::<Scalar>::deserialize(deserializer)? {
+        Some(Scalar::Text(text)) if text.trim().is_empty() => Ok(None),
+        Some(scalar) => scalar.into_number().map(|number| Some(number as f32)),
+        None => Ok(None),
+    }
+}
+
+/// Samples read with `f`, with `missing` in place of each `null` sample.
+fn numbers<'de, D: Deserializer<'de>, T: Clone>(
+    deserializer: D,
+    missing: T,
+    f: impl Fn(Scalar) -> Result<T, D::Error>,
+) -> Result<Vec<T>, D::Error> {
+    Option::<Vec<Option<Scalar>>>::deserialize(deserializer)?
+        .unwrap_or_default()
+        .into_iter()
+        .map(|scalar| match scalar {
+            Some(scalar) => f(scalar),
+            None => Ok(missing.clone()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize, Debug, PartialEq, Default)]
+    #[serde(default)]
+    struct Fields {
+        #[serde(deserialize_with = "text")]
+        text: String,
+        #[serde(deserialize_with = "texts")]
+        texts: Vec<String>,
+        #[serde(deserialize_with = "floats")]
+        floats: Vec<f32>,
+        #[serde(deserialize_with = "optional_float")]
+        optional_float: Option<f32>,
+    }
+
+    fn parse(json: &str) -> serde_json::Result<Fields> {
+        serde_json::from_str(json)
+    }
+
+    #[test]
+    fn test_accepts_strings_numbers_and_null() {
+        let fields = parse(
+            r#"{ "text": 18, "texts": ["0.0", 0.044, "-1"], "floats": [1, "2.5"], "optional_float": "9.8" }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            fields,
+            Fields {
+                text: "18".into(),
+                texts: vec!["0.0".into(), "0.044".into(), "-1".into()],
+                floats: vec![1.0, 2.5],
+                optional_float: Some(9.8),
+            }
+        );
+
+        let fields =
+            parse(r#"{ "text": null, "texts": null, "floats": null, "optional_float": null }"#)
+                .unwrap();
+        assert_eq!(fields, Fields::default());
+        assert_eq!(
+            parse(r#"{ "optional_float": " " }"#)
+                .unwrap()
+                .optional_float,
+            None
+        );
+        assert_eq!(
+            parse(r#"{ "extra": [1, "a"] }"#).unwrap(),
+            Fields::default()
+        );
+    }
+
+    #[test]
+    fn test_null_sample_is_missing() {
+        let fields = parse(r#"{ "texts": ["1", null], "floats": [1, null, 3] }"#).unwrap();
+        assert_eq!(fields.texts, ["1", ""]);
+        assert_eq!(fields.floats[0], 1.0);
+        assert!(fields.floats[1].is_nan());
+        assert_eq!(fields.floats[2], 3.0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_samples() {
+        let error = |json| parse(json).unwrap_err().to_string();
+        assert!(error(r#"{ "texts": ["0.0", "abc"] }"#).starts_with("invalid number \"abc\""));
+        assert!(error(r#"{ "floats": ["NaN"] }"#).starts_with("invalid number \"NaN\""));
+        assert!(error(r#"{ "optional_float": "lots" }"#).starts_with("invalid number"));
+        assert!(parse(r#"{ "texts": [true] }"#).is_err());
+    }
+}
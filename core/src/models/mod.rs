@@ -1,6 +1,11 @@
 mod chart_data;
+mod format;
 pub mod json;
+mod lenient;
 mod shot_data;
+pub mod tcl;
+pub mod visualizer;
 
 pub use chart_data::*;
+pub use format::ShotFormat;
 pub use shot_data::*;
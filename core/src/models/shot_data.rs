@@ -0,0 +1,438 @@
+use serde::{Deserialize, Serialize};
+
+/// Serialisable so that parsed shots can be stored; missing sections fall back
+/// to their defaults, so records stored by older builds keep loading.
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShotData {
+    pub version: String,
+    pub clock: usize,
+    pub date: String,
+    pub timestamp: usize,
+    pub elapsed: Vec<f32>,
+    pub pressure: PressureSection,
+    pub flow: FlowSection,
+    pub temperature: TemperatureSection,
+    pub totals: TotalsSection,
+    pub resistance: ResistanceSection,
+    pub state_change: Vec<f32>,
+    pub profile: ProfileSection,
+    pub meta: MetaSection,
+    pub app: AppSection,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct PressureSection {
+    pub pressure: Vec<f32>,
+    pub goal: Vec<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct FlowSection {
+    pub flow: Vec<f32>,
+    pub by_weight: Vec<f32>,
+    pub by_weight_raw: Vec<f32>,
+    pub goal: Vec<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TemperatureSection {
+    pub basket: Vec<f32>,
+    pub mix: Vec<f32>,
+    pub goal: Vec<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TotalsSection {
+    pub weight: Vec<f32>,
+    pub water_dispensed: Vec<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ResistanceSection {
+    pub resistance: Vec<f32>,
+    pub by_weight: Vec<f32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ProfileSection {
+    pub title: String,
+    /// Names of the profile steps, in the order they run.
+    pub steps: Vec<String>,
+}
+
+/// What the user logged about the shot. Numbers are 0 when not logged.
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct MetaSection {
+    pub bean_brand: String,
+    pub bean_type: String,
+    pub roast_level: String,
+    pub roast_date: String,
+    pub grinder_model: String,
+    pub grinder_setting: String,
+    /// Ground coffee in, in grams.
+    pub dose: f32,
+    /// Beverage out, in grams.
+    pub beverage: f32,
+    pub tds: f32,
+    pub ey: f32,
+    pub enjoyment: f32,
+    pub notes: String,
+}
+
+/// Display preferences of the DE1 app at the time of the shot.
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct AppSection {
+    pub fahrenheit: bool,
+    pub fluid_ounces: bool,
+}
+
+impl From<crate::models::json::ShotDataJson> for ShotData {
+    fn from(value: crate::models::json::ShotDataJson) -> Self {
+        Self {
+            version: value.version,
+            clock: to_usize_or_zero(&value.clock),
+            date: value.date,
+            timestamp: to_usize_or_zero(&value.timestamp),
+            elapsed: to_f32(value.elapsed),
+            pressure: PressureSection {
+                pressure: to_f32(value.pressure.pressure),
+                goal: to_f32(value.pressure.goal),
+            },
+            flow: FlowSection {
+                flow: to_f32(value.flow.flow),
+                by_weight: to_f32(value.flow.by_weight),
+                by_weight_raw: to_f32(value.flow.by_weight_raw),
+                goal: to_f32(value.flow.goal),
+            },
+            temperature: TemperatureSection {
+                basket: to_f32(value.temperature.basket),
+                mix: to_f32(value.temperature.mix),
+                goal: to_f32(value.temperature.goal),
+            },
+            totals: TotalsSection {
+                weight: to_f32(value.totals.weight),
+                water_dispensed: to_f32(value.totals.water_dispensed),
+            },
+            resistance: ResistanceSection {
+                resistance: to_f32(value.resistance.resistance),
+                by_weight: to_f32(value.resistance.by_weight),
+            },
+            state_change: to_f32(value.state_change),
+            profile: ProfileSection {
+                title: value.profile.title,
+                steps: value
+                    .profile
+                    .steps
+                    .into_iter()
+                    .map(|step| step.name)
+                    .collect(),
+            },
+            meta: MetaSection {
+                bean_brand: value.meta.bean.brand,
+                bean_type: value.meta.bean.kind,
+                roast_level: value.meta.bean.roast_level,
+                roast_date: value.meta.bean.roast_date,
+                grinder_model: value.meta.grinder.model,
+                grinder_setting: value.meta.grinder.setting,
+                dose: to_f32_or_zero(&value.meta.dose),
+                beverage: to_f32_or_zero(&value.meta.beverage),
+                tds: to_f32_or_zero(&value.meta.shot.tds),
+                ey: to_f32_or_zero(&value.meta.shot.ey),
+                enjoyment: to_f32_or_zero(&value.meta.shot.enjoyment),
+                notes: value.meta.shot.notes,
+            },
+            app: AppSection {
+                fahrenheit: value.app.data.settings.enable_fahrenheit == "1",
+                fluid_ounces: value.app.data.settings.enable_fluid_ounces == "1",
+            },
+        }
+    }
+}
+
+impl ShotData {
+    /// Index of the profile step each sample was recorded in. The DE1 flips the
+    /// sign of `state_change` every time it moves on to the next step.
+    pub fn phases(&self) -> Vec<usize> {
+        let mut phase = 0;
+        let mut sign = 0.0;
+        self.state_change
+            .iter()
+            .map(|v| {
+                if *v != 0.0 {
+                    if sign != 0.0 && v.signum() != sign {
+                        phase += 1;
+                    }
+                    sign = v.signum();
+                }
+                phase
+            })
+            .collect()
+    }
+
+    /// Name of the profile step running at `t` seconds, if the profile names it.
+    pub fn phase_at(&self, t: f32) -> Option<&str> {
+        let i = self
+            .elapsed
+            .iter()
+            .take_while(|elapsed| **elapsed <= t)
+            .count()
+            .checked_sub(1)?;
+        let phase = *self.phases().get(i)?;
+        self.profile.steps.get(phase).map(String::as_str)
+    }
+
+    /// Every sampled series, in a fixed order.
+    fn series(&self) -> [&[f32]; 15] {
+        [
+            &self.elapsed,
+            &self.pressure.pressure,
+            &self.pressure.goal,
+            &self.flow.flow,
+            &self.flow.by_weight,
+            &self.flow.by_weight_raw,
+            &self.flow.goal,
+            &self.temperature.basket,
+            &self.temperature.mix,
+            &self.temperature.goal,
+            &self.totals.weight,
+            &self.totals.water_dispensed,
+            &self.resistance.resistance,
+            &self.resistance.by_weight,
+            &self.state_change,
+        ]
+    }
+
+    /// Tells whether `other` is this shot with at most its logged metadata
+    /// edited, i.e. whether charts drawn from either would be the same.
+    pub fn same_samples(&self, other: &Self) -> bool {
+        // Gaps are NaN, so the series are compared bit by bit.
+        let same = |a: &[f32], b: &[f32]| {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.to_bits() == b.to_bits())
+        };
+        (&self.version, self.clock, &self.date, self.timestamp)
+            == (&other.version, other.clock, &other.date, other.timestamp)
+            && self.profile == other.profile
+            && self.app == other.app
+            && self
+                .series()
+                .into_iter()
+                .zip(other.series())
+                .all(|(a, b)| same(a, b))
+    }
+}
+
+/// Missing or unreadable samples read as NaN, which is drawn as a gap.
+fn to_f32(src: Vec<String>) -> Vec<f32> {
+    src.into_iter()
+        .map(|s| s.trim().parse().unwrap_or(f32::NAN))
+        .collect()
+}
+
+/// Clocks written as `1683084174.0` or left out read as 0.
+fn to_usize_or_zero(src: &str) -> usize {
+    src.trim().parse::<f64>().map_or(0, |value| value as usize)
+}
+
+/// Logged values are free text and often left empty.
+fn to_f32_or_zero(src: &str) -> f32 {
+    src.trim().parse().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{json::*, ChartData, DataPoint};
+
+    #[test]
+    fn test_convert_shot_data_from_json() {
+        let json = ShotDataJson {
+            version: "2".into(),
+            clock: "1685582363".into(),
+            date: "Thu Jun 01 10:19:23 JST 2023".into(),
+            timestamp: "1685582363".into(),
+            elapsed: vec!["0.0".into(), "0.044".into(), "0.268".into()],
+            pressure: PressureSectionJson {
+                pressure: vec!["0.0".into(), "0.0".into(), "0.0".into()],
+                goal: vec!["-1.0".into(), "-1.0".into(), "-1.0".into()],
+            },
+            flow: FlowSectionJson {
+                flow: vec!["0.0".into(), "3.26".into(), "3.98".into()],
+                by_weight: vec!["0.0".into(), "0.0".into(), "0.0".into()],
+                by_weight_raw: vec!["0.0".into(), "0.0".into(), "0.0".into()],
+                goal: vec!["-1.0".into(), "6.75".into(), "7.1875".into()],
+            },
+            temperature: TemperatureSectionJson {
+                basket: vec!["87.0".into(), "85.07".into(), "85.37".into()],
+                mix: vec!["87.0".into(), "87.17".into(), "87.14".into()],
+                goal: vec!["87.0".into(), "87.0".into(), "87.0".into()],
+            },
+            totals: TotalsSectionJson {
+                weight: vec!["0.0".into(), "0.0".into(), "0.0".into()],
+                water_dispensed: vec![
+                    "0.0".into(),
+                    "0.08161010742187501".into(),
+                    "0.181060791015625".into(),
+                ],
+            },
+            resistance: ResistanceSectionJson {
+                resistance: vec!["0.0".into(), "0.0".into(), "0.0".into()],
+                by_weight: vec!["0.0".into(), "0.0".into(), "0.0".into()],
+            },
+            state_change: vec!["0.0".into(), "10000000.0".into(), "10000000.0".into()],
+            profile: ProfileJson {
+                title: "TurboBloom 87c".into(),
+                steps: vec![StepJson {
+                    name: "preinfusion".into(),
+                }],
+            },
+            meta: MetaJson {
+                bean: BeanJson {
+                    brand: "Wakaki Coffee".into(),
+                    ..Default::default()
+                },
+                dose: "20.0".into(),
+                beverage: "".into(),
+                ..Default::default()
+            },
+            app: AppJson {
+                data: AppDataJson {
+                    settings: AppSettingsJson {
+                        enable_fahrenheit: "1".into(),
+                        enable_fluid_ounces: "0".into(),
+                    },
+                },
+            },
+        };
+        let actual: ShotData = json.into();
+        let expected = ShotData {
+            version: "2".into(),
+            clock: 1685582363,
+            date: "Thu Jun 01 10:19:23 JST 2023".into(),
+            timestamp: 1685582363,
+            elapsed: vec![0.0, 0.044, 0.268],
+            pressure: PressureSection {
+                pressure: vec![0.0, 0.0, 0.0],
+                goal: vec![-1.0, -1.0, -1.0],
+            },
+            flow: FlowSection {
+                flow: vec![0.0, 3.26, 3.98],
+                by_weight: vec![0.0, 0.0, 0.0],
+                by_weight_raw: vec![0.0, 0.0, 0.0],
+                goal: vec![-1.0, 6.75, 7.1875],
+            },
+            temperature: TemperatureSection {
+                basket: vec![87.0, 85.07, 85.37],
+                mix: vec![87.0, 87.17, 87.14],
+                goal: vec![87.0, 87.0, 87.0],
+            },
+            totals: TotalsSection {
+                weight: vec![0.0, 0.0, 0.0],
+                water_dispensed: vec![0.0, 0.081_610_106, 0.181_060_79],
+            },
+            resistance: ResistanceSection {
+                resistance: vec![0.0, 0.0, 0.0],
+                by_weight: vec![0.0, 0.0, 0.0],
+            },
+            state_change: vec![0.0, 10000000.0, 10000000.0],
+            profile: ProfileSection {
+                title: "TurboBloom 87c".into(),
+                steps: vec!["preinfusion".into()],
+            },
+            meta: MetaSection {
+                bean_brand: "Wakaki Coffee".into(),
+                dose: 20.0,
+                ..Default::default()
+            },
+            app: AppSection {
+                fahrenheit: true,
+                fluid_ounces: false,
+            },
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_convert_mixed_shot_data() {
+        let json = include_str!("../../../data/mixed.json");
+        let data: ShotData = serde_json::from_str::<ShotDataJson>(json).unwrap().into();
+        assert_eq!(data.version, "2");
+        assert_eq!((data.clock, data.timestamp), (1685582363, 1685582363));
+        assert_eq!(data.elapsed, vec![0.0, 0.044, 0.268, 0.5]);
+        assert_eq!(data.pressure.pressure, vec![0.0, 1.2, 3.4, 6.0]);
+        assert!(data.pressure.goal.is_empty());
+        assert!(data.flow.by_weight_raw.is_empty());
+        assert_eq!(data.flow.goal, vec![-1.0, 6.75, 7.1875, -1.0]);
+        assert_eq!(data.totals.water_dispensed[1], 0.081_610_106);
+        assert_eq!(data.state_change[3], -10000000.0);
+        assert_eq!(data.meta.grinder_setting, "7.5");
+        assert_eq!((data.meta.dose, data.meta.beverage), (18.0, 36.4));
+        assert_eq!((data.meta.tds, data.meta.ey), (0.0, 0.0));
+        assert_eq!(data.meta.enjoyment, 80.0);
+        assert_eq!(
+            data.app,
+            AppSection {
+                fahrenheit: false,
+                fluid_ounces: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_reject_invalid_samples() {
+        let json = include_str!("../../../data/mixed.json").replace("\"0.044\"", "\"fast\"");
+        let error = serde_json::from_str::<ShotDataJson>(&json).unwrap_err();
+        assert!(error.to_string().starts_with("invalid number \"fast\""));
+    }
+
+    #[test]
+    fn test_null_sample_is_a_gap() {
+        let json = include_str!("../../../data/mixed.json").replace("\"3.4\"", "null");
+        let data: ShotData = serde_json::from_str::<ShotDataJson>(&json).unwrap().into();
+        let chart = ChartData::pressure(&data);
+        assert_eq!(chart.series[2], DataPoint::NoData(0.268));
+        assert_eq!(chart.series[3], DataPoint::Present((0.5, 6.0)));
+    }
+
+    #[test]
+    fn test_unreadable_sample_is_nan() {
+        let samples = to_f32(vec!["1.5".into(), "fast".into(), "".into()]);
+        assert_eq!(samples[0], 1.5);
+        assert!(samples[1].is_nan() && samples[2].is_nan());
+    }
+
+    #[test]
+    fn test_shot_data_round_trip() {
+        let data: ShotData = serde_json::from_str::<ShotDataJson>(SHOT2).unwrap().into();
+        let json = serde_json::to_string(&data).unwrap();
+        assert_eq!(serde_json::from_str::<ShotData>(&json).unwrap(), data);
+    }
+
+    #[test]
+    fn test_phases() {
+        let data = ShotData {
+            elapsed: vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+            state_change: vec![0.0, 1e7, 1e7, -1e7, -1e7, 1e7],
+            profile: ProfileSection {
+                title: "".into(),
+                steps: vec!["preinfusion".into(), "pour".into()],
+            },
+            ..Default::default()
+        };
+        assert_eq!(data.phases(), vec![0, 0, 0, 1, 1, 2]);
+        assert_eq!(data.phase_at(-1.0), None);
+        assert_eq!(data.phase_at(2.5), Some("preinfusion"));
+        assert_eq!(data.phase_at(3.0), Some("pour"));
+        assert_eq!(data.phase_at(5.0), None);
+    }
+
+    #[test]
+    fn test_phases_of_shot2() {
+        let data: ShotData = serde_json::from_str::<ShotDataJson>(SHOT2).unwrap().into();
+        let phases = data.phases();
+        assert_eq!(phases.len(), data.elapsed.len());
+        assert_eq!(*phases.last().unwrap() + 1, data.profile.steps.len());
+        assert_eq!(data.meta.grinder_model, "P64 Mizen");
+        assert_eq!((data.meta.dose, data.meta.beverage), (20.0, 40.4));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+/// Appends the character escaped by the backslash at `chars[*i]` and moves past it.
+fn unescape(chars: &[char], i: &mut usize, out: &mut String) {
+    *i += 1;
+    match chars.get(*i) {
+        Some('n') => out.push('\n'),
+        Some('t') => out.push('\t'),
+        Some('r') => out.push('\r'),
+        // A backslash-newline and the indentation after it read as one space.
+        Some('\n') => {
+            out.push(' ');
+            while matches!(chars.get(*i + 1), Some(' ' | '\t')) {
+                *i += 1;
+            }
+        }
+        Some(c) => out.push(*c),
+        None => {
+            out.push('\\');
+            return;
+        }
+    }
+    *i += 1;
+}
+
+/// Contents of the braces opening at `chars[*i]`, taken literally.
+fn braced(chars: &[char], i: &mut usize) -> Result<String, String> {
+    let mut out = String::default();
+    let mut depth = 0;
+    while let Some(c) = chars.get(*i) {
+        match c {
+            '\\' => {
+                out.push('\\');
+                if let Some(next) = chars.get(*i + 1) {
+                    out.push(*next);
+                    *i += 1;
+                }
+            }
+            '{' => {
+                depth += 1;
+                if depth == 1 {
+                    *i += 1;
+                    continue;
+                }
+                out.push('{');
+            }
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    *i += 1;
+                    return Ok(out);
+                }
+                out.push('}');
+            }
+            c => out.push(*c),
+        }
+        *i += 1;
+    }
+    Err("unmatched open brace in list".into())
+}
+
+/// Contents of the quotes opening at `chars[*i]`, with backslashes substituted.
+fn quoted(chars: &[char], i: &mut usize) -> Result<String, String> {
+    let mut out = String::default();
+    *i += 1;
+    while let Some(c) = chars.get(*i) {
+        match c {
+            '\\' => unescape(chars, i, &mut out),
+            '"' => {
+                *i += 1;
+                return Ok(out);
+            }
+            c => {
+                out.push(*c);
+                *i += 1;
+            }
+        }
+    }
+    Err("unmatched open quote in list".into())
+}
+
+fn bare(chars: &[char], i: &mut usize) -> String {
+    let mut out = String::default();
+    while let Some(c) = chars.get(*i) {
+        match c {
+            '\\' => unescape(chars, i, &mut out),
+            c if c.is_whitespace() => break,
+            c => {
+                out.push(*c);
+                *i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Splits a Tcl list into its elements. Nested lists come back as text, to be
+/// split again by the caller.
+pub fn parse_list(text: &str) -> Result<Vec<String>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut elements = vec![];
+    let mut i = 0;
+    loop {
+        while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+            i += 1;
+        }
+        let element = match chars.get(i) {
+            None => return Ok(elements),
+            Some('{') => braced(&chars, &mut i)?,
+            Some('"') => quoted(&chars, &mut i)?,
+            Some(_) => bare(&chars, &mut i),
+        };
+        if let Some(c) = chars.get(i).filter(|c| !c.is_whitespace()) {
+            return Err(format!(
+                "list element \"{}\" followed by \"{}\" instead of space",
+                element, c
+            ));
+        }
+        elements.push(element);
+    }
+}
+
+/// Reads a Tcl dict, i.e. a list of alternating keys and values. A key given
+/// twice keeps its last value, as in Tcl.
+pub fn parse_dict(text: &str) -> Result<HashMap<String, String>, String> {
+    let elements = parse_list(text)?;
+    if elements.len() % 2 != 0 {
+        return Err("missing value to go with key".into());
+    }
+    let mut dict = HashMap::new();
+    let mut elements = elements.into_iter();
+    while let (Some(key), Some(value)) = (elements.next(), elements.next()) {
+        dict.insert(key, value);
+    }
+    Ok(dict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(parse_list("  ").unwrap(), Vec::<String>::new());
+        assert_eq!(
+            parse_list("0.0 0.044\n\t0.268").unwrap(),
+            ["0.0", "0.044", "0.268"]
+        );
+        assert_eq!(
+            parse_list(r#"a {b {c d}} "e \"f\"" {} g\ h"#).unwrap(),
+            ["a", "b {c d}", "e \"f\"", "", "g h"]
+        );
+        assert_eq!(parse_list(r"{a\}b}").unwrap(), [r"a\}b"]);
+        assert_eq!(parse_list("\"a\\\n    b\"").unwrap(), ["a b"]);
+    }
+
+    #[test]
+    fn test_parse_list_errors() {
+        assert_eq!(
+            parse_list("a {b {c}").unwrap_err(),
+            "unmatched open brace in list"
+        );
+        assert_eq!(
+            parse_list("a \"b").unwrap_err(),
+            "unmatched open quote in list"
+        );
+        assert_eq!(
+            parse_list("{a}b").unwrap_err(),
+            "list element \"a\" followed by \"b\" instead of space"
+        );
+    }
+
+    #[test]
+    fn test_parse_dict() {
+        let dict = parse_dict("name {Blooming espresso} seconds 25 name Default").unwrap();
+        assert_eq!(dict.len(), 2);
+        assert_eq!(dict["name"], "Default");
+        assert_eq!(dict["seconds"], "25");
+        assert!(parse_dict("name").is_err());
+    }
+}
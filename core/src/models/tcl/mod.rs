@@ -0,0 +1,5 @@
+mod list;
+mod shot_data;
+
+pub use list::{parse_dict, parse_list};
+pub use shot_data::parse_shot;
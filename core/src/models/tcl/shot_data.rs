@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use super::{parse_dict, parse_list};
+use crate::models::{
+    AppSection, FlowSection, MetaSection, PressureSection, ProfileSection, ResistanceSection,
+    ShotData, TemperatureSection, TotalsSection,
+};
+
+type Dict = HashMap<String, String>;
+
+/// Samples of one series. Older app versions don't record every series, so a
+/// missing one is left empty.
+fn series(dict: &Dict, key: &str) -> Result<Vec<f32>, String> {
+    let Some(text) = dict.get(key) else {
+        return Ok(vec![]);
+    };
+    parse_list(text)
+        .map_err(|e| format!("{}: {}", key, e))?
+        .iter()
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("{}: invalid number \"{}\"", key, value))
+        })
+        .collect()
+}
+
+fn text(dict: &Dict, key: &str) -> String {
+    dict.get(key).cloned().unwrap_or_default()
+}
+
+/// Logged values are free text and often left empty.
+fn number(dict: &Dict, key: &str) -> f32 {
+    text(dict, key).trim().parse().unwrap_or_default()
+}
+
+/// Names of the steps of an advanced profile; simple profiles have none.
+fn steps(settings: &Dict) -> Result<Vec<String>, String> {
+    parse_list(&text(settings, "advanced_shot"))?
+        .iter()
+        .map(|step| parse_dict(step).map(|step| text(&step, "name")))
+        .collect()
+}
+
+/// Parses a shot saved by de1app versions before the JSON history format: a
+/// Tcl dict of sample lists, with the profile and the logged values under `settings`.
+pub fn parse_shot(source: &str) -> Result<ShotData, String> {
+    let shot = parse_dict(source)?;
+    if !shot.contains_key("espresso_elapsed") {
+        return Err("not a shot file: missing espresso_elapsed".into());
+    }
+    let settings = match shot.get("settings") {
+        Some(settings) => parse_dict(settings).map_err(|e| format!("settings: {}", e))?,
+        None => Dict::default(),
+    };
+    let clock = text(&shot, "clock").trim().parse().unwrap_or_default();
+
+    Ok(ShotData {
+        version: "1".into(),
+        clock,
+        date: text(&shot, "local_time"),
+        timestamp: clock,
+        elapsed: series(&shot, "espresso_elapsed")?,
+        pressure: PressureSection {
+            pressure: series(&shot, "espresso_pressure")?,
+            goal: series(&shot, "espresso_pressure_goal")?,
+        },
+        flow: FlowSection {
+            flow: series(&shot, "espresso_flow")?,
+            by_weight: series(&shot, "espresso_flow_weight")?,
+            by_weight_raw: series(&shot, "espresso_flow_weight_raw")?,
+            goal: series(&shot, "espresso_flow_goal")?,
+        },
+        temperature: TemperatureSection {
+            basket: series(&shot, "espresso_temperature_basket")?,
+            mix: series(&shot, "espresso_temperature_mix")?,
+            goal: series(&shot, "espresso_temperature_goal")?,
+        },
+        totals: TotalsSection {
+            weight: series(&shot, "espresso_weight")?,
+            water_dispensed: series(&shot, "espresso_water_dispensed")?,
+        },
+        resistance: ResistanceSection {
+            resistance: series(&shot, "espresso_resistance")?,
+            by_weight: series(&shot, "espresso_resistance_weight")?,
+        },
+        state_change: series(&shot, "espresso_state_change")?,
+        profile: ProfileSection {
+            title: text(&settings, "profile_title"),
+            steps: steps(&settings).map_err(|e| format!("advanced_shot: {}", e))?,
+        },
+        meta: MetaSection {
+            bean_brand: text(&settings, "bean_brand"),
+            bean_type: text(&settings, "bean_type"),
+            roast_level: text(&settings, "roast_level"),
+            roast_date: text(&settings, "roast_date"),
+            grinder_model: text(&settings, "grinder_model"),
+            grinder_setting: text(&settings, "grinder_setting"),
+            dose: number(&settings, "grinder_dose_weight"),
+            beverage: number(&settings, "drink_weight"),
+            tds: number(&settings, "drink_tds"),
+            ey: number(&settings, "drink_ey"),
+            enjoyment: number(&settings, "espresso_enjoyment"),
+            notes: text(&settings, "espresso_notes"),
+        },
+        app: AppSection {
+            fahrenheit: text(&settings, "enable_fahrenheit") == "1",
+            fluid_ounces: text(&settings, "enable_fluid_ounces") == "1",
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADVANCED: &str = include_str!("../../../../data/legacy/advanced.shot");
+    const SIMPLE: &str = include_str!("../../../../data/legacy/simple.shot");
+    const UNBALANCED: &str = include_str!("../../../../data/legacy/unbalanced.shot");
+    const GARBLED: &str = include_str!("../../../../data/legacy/garbled.shot");
+
+    #[test]
+    fn test_parse_advanced_shot() {
+        let data = parse_shot(ADVANCED).unwrap();
+        assert_eq!(data.timestamp, 1589456473);
+        assert_eq!(data.date, "Thu May 14 13:41:13 CEST 2020");
+        assert_eq!(data.elapsed.len(), 10);
+        for series in [
+            &data.pressure.pressure,
+            &data.pressure.goal,
+            &data.flow.flow,
+            &data.flow.by_weight,
+            &data.temperature.basket,
+            &data.totals.weight,
+            &data.resistance.by_weight,
+            &data.state_change,
+        ] {
+            assert_eq!(series.len(), data.elapsed.len());
+        }
+        assert_eq!(data.pressure.pressure[9], 8.9);
+        assert_eq!(data.pressure.goal[0], -1.0);
+
+        assert_eq!(data.profile.title, "Blooming espresso");
+        assert_eq!(data.profile.steps, ["preinfuse at 4 ml/s", "pour \"slow\""]);
+        assert_eq!(data.phase_at(2.0), Some("pour \"slow\""));
+
+        assert_eq!(
+            data.meta,
+            MetaSection {
+                bean_brand: "Square Mile".into(),
+                bean_type: "Red Brick".into(),
+                roast_level: "Medium".into(),
+                roast_date: "2020-05-01".into(),
+                grinder_model: "Niche Zero".into(),
+                grinder_setting: "14".into(),
+                dose: 18.0,
+                beverage: 36.4,
+                tds: 9.8,
+                ey: 19.8,
+                enjoyment: 75.0,
+                notes: "Sweet, a little {sour} at the end".into(),
+            }
+        );
+        assert_eq!(
+            data.app,
+            AppSection {
+                fahrenheit: false,
+                fluid_ounces: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_simple_shot() {
+        let data = parse_shot(SIMPLE).unwrap();
+        assert_eq!(data.timestamp, 1576573513);
+        assert_eq!(data.elapsed, [0.0, 0.5, 1.0, 1.5]);
+        assert_eq!(data.pressure.pressure, [0.0, 2.0, 6.0, 9.0]);
+        assert!(data.totals.weight.is_empty());
+        assert!(data.resistance.resistance.is_empty());
+        assert_eq!(data.profile.title, "Default");
+        assert!(data.profile.steps.is_empty());
+        assert_eq!(data.meta, MetaSection::default());
+        assert!(data.app.fahrenheit);
+    }
+
+    #[test]
+    fn test_parse_broken_shots() {
+        assert_eq!(
+            parse_shot(UNBALANCED).unwrap_err(),
+            "unmatched open brace in list"
+        );
+        assert_eq!(
+            parse_shot(GARBLED).unwrap_err(),
+            "espresso_elapsed: invalid number \"one\""
+        );
+        assert_eq!(
+            parse_shot("clock 1576573513").unwrap_err(),
+            "not a shot file: missing espresso_elapsed"
+        );
+    }
+}
@@ -0,0 +1,3 @@
+mod shot_data;
+
+pub use shot_data::*;
@@ -0,0 +1,253 @@
+use serde::Deserialize;
+
+use crate::models::{
+    lenient, FlowSection, MetaSection, PressureSection, ProfileSection, ResistanceSection,
+    ShotData, TemperatureSection, TotalsSection,
+};
+
+/// Shot as downloaded from visualizer.coffee, with the time axis in
+/// `timeframe` and the series under `data`.
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct ShotDataVisualizer {
+    pub profile_title: String,
+    /// ISO 8601, e.g. `2023-05-03T03:22:54.000Z`.
+    pub start_time: String,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub timeframe: Vec<f32>,
+    pub data: VisualizerSeries,
+    pub bean_brand: Option<String>,
+    pub bean_type: Option<String>,
+    pub roast_level: Option<String>,
+    pub roast_date: Option<String>,
+    pub grinder_model: Option<String>,
+    pub grinder_setting: Option<String>,
+    #[serde(deserialize_with = "lenient::optional_float")]
+    pub bean_weight: Option<f32>,
+    #[serde(deserialize_with = "lenient::optional_float")]
+    pub drink_weight: Option<f32>,
+    #[serde(deserialize_with = "lenient::optional_float")]
+    pub drink_tds: Option<f32>,
+    #[serde(deserialize_with = "lenient::optional_float")]
+    pub drink_ey: Option<f32>,
+    #[serde(deserialize_with = "lenient::optional_float")]
+    pub espresso_enjoyment: Option<f32>,
+    pub espresso_notes: Option<String>,
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(default)]
+pub struct VisualizerSeries {
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_pressure: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_pressure_goal: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_flow: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_flow_weight: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_flow_weight_raw: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_flow_goal: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_temperature_basket: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_temperature_mix: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_temperature_goal: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_weight: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_water_dispensed: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_resistance: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_resistance_weight: Vec<f32>,
+    #[serde(deserialize_with = "lenient::floats")]
+    pub espresso_state_change: Vec<f32>,
+}
+
+/// Days from 1970-01-01 to the given date of the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Seconds since the epoch of a `YYYY-MM-DDTHH:MM:SS[.fff](Z|±HH:MM)` time.
+fn unix_time(iso: &str) -> Option<usize> {
+    let field = |range: std::ops::Range<usize>| -> Option<i64> { iso.get(range)?.parse().ok() };
+    let days = days_from_civil(field(0..4)?, field(5..7)?, field(8..10)?);
+    let seconds = field(11..13)? * 3600 + field(14..16)? * 60 + field(17..19)?;
+
+    let zone = iso
+        .get(19..)?
+        .trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset = match zone.chars().next()? {
+        'Z' => 0,
+        sign @ ('+' | '-') => {
+            let minutes =
+                zone.get(1..3)?.parse::<i64>().ok()? * 60 + zone.get(4..6)?.parse::<i64>().ok()?;
+            if sign == '+' {
+                minutes * 60
+            } else {
+                -minutes * 60
+            }
+        }
+        _ => return None,
+    };
+    usize::try_from(days * 86_400 + seconds - offset).ok()
+}
+
+impl From<ShotDataVisualizer> for ShotData {
+    fn from(value: ShotDataVisualizer) -> Self {
+        let timestamp = unix_time(&value.start_time).unwrap_or_default();
+        let data = value.data;
+        Self {
+            version: "visualizer".into(),
+            clock: timestamp,
+            date: value.start_time,
+            timestamp,
+            elapsed: value.timeframe,
+            pressure: PressureSection {
+                pressure: data.espresso_pressure,
+                goal: data.espresso_pressure_goal,
+            },
+            flow: FlowSection {
+                flow: data.espresso_flow,
+                by_weight: data.espresso_flow_weight,
+                by_weight_raw: data.espresso_flow_weight_raw,
+                goal: data.espresso_flow_goal,
+            },
+            temperature: TemperatureSection {
+                basket: data.espresso_temperature_basket,
+                mix: data.espresso_temperature_mix,
+                goal: data.espresso_temperature_goal,
+            },
+            totals: TotalsSection {
+                weight: data.espresso_weight,
+                water_dispensed: data.espresso_water_dispensed,
+            },
+            resistance: ResistanceSection {
+                resistance: data.espresso_resistance,
+                by_weight: data.espresso_resistance_weight,
+            },
+            state_change: data.espresso_state_change,
+            profile: ProfileSection {
+                title: value.profile_title,
+                steps: vec![],
+            },
+            meta: MetaSection {
+                bean_brand: value.bean_brand.unwrap_or_default(),
+                bean_type: value.bean_type.unwrap_or_default(),
+                roast_level: value.roast_level.unwrap_or_default(),
+                roast_date: value.roast_date.unwrap_or_default(),
+                grinder_model: value.grinder_model.unwrap_or_default(),
+                grinder_setting: value.grinder_setting.unwrap_or_default(),
+                dose: value.bean_weight.unwrap_or_default(),
+                beverage: value.drink_weight.unwrap_or_default(),
+                tds: value.drink_tds.unwrap_or_default(),
+                ey: value.drink_ey.unwrap_or_default(),
+                enjoyment: value.espresso_enjoyment.unwrap_or_default(),
+                notes: value.espresso_notes.unwrap_or_default(),
+            },
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHOT: &str = include_str!("../../../../data/visualizer/shot.json");
+    const MINIMAL: &str = include_str!("../../../../data/visualizer/minimal.json");
+
+    fn parse(json: &str) -> ShotData {
+        serde_json::from_str::<ShotDataVisualizer>(json)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn test_unix_time() {
+        assert_eq!(unix_time("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(unix_time("2023-05-03T03:22:54.000Z"), Some(1683084174));
+        assert_eq!(unix_time("2023-05-03T12:22:54+09:00"), Some(1683084174));
+        assert_eq!(unix_time("2000-02-29T00:00:00-00:30"), Some(951784200));
+        assert_eq!(unix_time("2023-05-03"), None);
+        assert_eq!(unix_time("yesterday"), None);
+    }
+
+    #[test]
+    fn test_convert_visualizer_shot() {
+        let data = parse(SHOT);
+        assert_eq!(data.timestamp, 1683084174);
+        assert_eq!(data.date, "2023-05-03T03:22:54.000Z");
+        assert_eq!(data.elapsed, [0.0, 0.25, 0.5, 0.75, 1.0, 1.25, 1.5, 1.75]);
+        for series in [
+            &data.pressure.pressure,
+            &data.pressure.goal,
+            &data.flow.flow,
+            &data.flow.by_weight,
+            &data.temperature.mix,
+            &data.totals.weight,
+            &data.resistance.resistance,
+            &data.state_change,
+        ] {
+            assert_eq!(series.len(), data.elapsed.len());
+        }
+        assert_eq!(data.pressure.pressure[7], 8.8);
+        assert_eq!(data.profile.title, "Extractamundo Dos!");
+        assert_eq!(
+            data.meta,
+            MetaSection {
+                bean_brand: "Wakaki Coffee".into(),
+                bean_type: "Ethiopia Worka".into(),
+                roast_level: "Light".into(),
+                roast_date: "2023-04-20".into(),
+                grinder_model: "Lagom P64".into(),
+                grinder_setting: "7.5".into(),
+                dose: 18.2,
+                beverage: 40.1,
+                tds: 9.1,
+                ey: 20.4,
+                enjoyment: 80.0,
+                notes: "Juicy".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_visualizer_shot_with_strings() {
+        let json = r#"{
+            "start_time": "2023-05-03T03:22:54Z",
+            "timeframe": ["0.0", 0.5],
+            "data": { "espresso_pressure": [0, "4.5"], "espresso_flow": null },
+            "bean_weight": "18.0",
+            "drink_tds": "",
+            "drink_ey": null,
+            "espresso_enjoyment": 72
+        }"#;
+        let data = parse(json);
+        assert_eq!(data.elapsed, [0.0, 0.5]);
+        assert_eq!(data.pressure.pressure, [0.0, 4.5]);
+        assert!(data.flow.flow.is_empty());
+        assert_eq!(data.meta.dose, 18.0);
+        assert_eq!((data.meta.tds, data.meta.ey), (0.0, 0.0));
+        assert_eq!(data.meta.enjoyment, 72.0);
+    }
+
+    #[test]
+    fn test_convert_minimal_visualizer_shot() {
+        let data = parse(MINIMAL);
+        assert_eq!(data.timestamp, 1576569913);
+        assert_eq!(data.flow.flow, [0.0, 3.0, 2.0]);
+        assert!(data.totals.weight.is_empty());
+        assert_eq!(data.meta, MetaSection::default());
+    }
+}
@@ -0,0 +1,368 @@
+use std::fmt::Write;
+use std::rc::Rc;
+
+use crate::{
+    models::{ChartData, SeriesKind},
+    Axis, Hud, Path, Range, Rect, Settings, Theme, Units, TICK_LENGTH,
+};
+
+const PANEL_PADDING: f32 = 8.0;
+const PANEL_RADIUS: f32 = 8.0;
+const AXIS_STEP: f32 = 10.0;
+pub const AXIS_LABEL_SIZE: f32 = 10.0;
+pub const HUD_FONT_SIZE: f32 = 12.0;
+const STROKE_WIDTH: f32 = 1.5;
+const REFERENCE_STROKE_WIDTH: f32 = 1.0;
+const REFERENCE_STROKE_OPACITY: f32 = 0.5;
+
+/// Vertical band of each series relative to the chart block.
+fn band(kind: SeriesKind) -> (f32, f32) {
+    match kind {
+        SeriesKind::Pressure => (0.75, 0.0),
+        SeriesKind::Flow | SeriesKind::FlowByWeight => (0.75, 0.45),
+        SeriesKind::TempBasket | SeriesKind::TempMix => (1.0, 0.85),
+    }
+}
+
+/// Rounded background behind the chart block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Panel {
+    pub rect: Rect,
+    pub radius: f32,
+    pub fill: &'static str,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stroke {
+    pub color: &'static str,
+    pub width: f32,
+    /// On top of the opacity of the whole overlay.
+    pub opacity: f32,
+    pub path: Path,
+}
+
+/// Everything drawn over one video frame, in pixels of that frame.
+/// Every rendering backend and exporter draws from this.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overlay {
+    pub size: (f32, f32),
+    pub opacity: f32,
+    pub theme: Theme,
+    pub panel: Option<Panel>,
+    pub axis: Axis,
+    pub hud: Hud,
+    pub strokes: Vec<Stroke>,
+}
+
+impl Overlay {
+    /// Lays out `charts` (already converted into `units`) as they look `t` seconds into the shot.
+    pub fn build(
+        charts: &[(SeriesKind, Rc<ChartData>)],
+        units: &Units,
+        time_span: &Range,
+        t: f32,
+        size: (f32, f32),
+        settings: &Settings,
+    ) -> Self {
+        Self::build_compared(charts, &[], units, time_span, t, size, settings)
+    }
+
+    /// Same as [`Overlay::build`], with the series of a reference shot drawn
+    /// thinner and dimmer under those of the shot, e.g. from a [`crate::Comparison`].
+    pub fn build_compared(
+        charts: &[(SeriesKind, Rc<ChartData>)],
+        reference: &[(SeriesKind, Rc<ChartData>)],
+        units: &Units,
+        time_span: &Range,
+        t: f32,
+        size: (f32, f32),
+        settings: &Settings,
+    ) -> Self {
+        let enabled = |charts: &[(SeriesKind, Rc<ChartData>)]| -> Vec<_> {
+            charts
+                .iter()
+                .filter(|(kind, _)| settings.series.contains(kind))
+                .cloned()
+                .collect()
+        };
+        let charts = enabled(charts);
+        let reference = enabled(reference);
+        let theme = settings.theme.theme();
+        let block = settings.placement.rect().scale(size);
+        let hud = Hud::build(&charts, &reference, units, t, &block, HUD_FONT_SIZE);
+        let area = Rect::new(
+            block.x,
+            block.y + hud.height,
+            block.width,
+            block.height - hud.height,
+        );
+        let axis = Axis::time(time_span.as_tuple(), &area, AXIS_STEP);
+        let panel = settings.panel.then(|| {
+            let mut rect = block.inflate(PANEL_PADDING);
+            rect.height += TICK_LENGTH + AXIS_LABEL_SIZE;
+            Panel {
+                rect,
+                radius: PANEL_RADIUS,
+                fill: theme.panel,
+            }
+        });
+        let stroke = |kind: SeriesKind, data: &ChartData, width: f32, opacity: f32| {
+            let (top, bottom) = band(kind);
+            Stroke {
+                color: theme.color(kind),
+                width,
+                opacity,
+                path: Path::build(
+                    data,
+                    time_span.as_tuple(),
+                    (area.x, area.x + area.width),
+                    (area.y + top * area.height, area.y + bottom * area.height),
+                    t,
+                ),
+            }
+        };
+        let strokes = reference
+            .iter()
+            .map(|(kind, data)| {
+                stroke(
+                    *kind,
+                    data,
+                    REFERENCE_STROKE_WIDTH,
+                    REFERENCE_STROKE_OPACITY,
+                )
+            })
+            .chain(
+                charts
+                    .iter()
+                    .map(|(kind, data)| stroke(*kind, data, STROKE_WIDTH, 1.)),
+            )
+            .collect();
+
+        Self {
+            size,
+            opacity: settings.opacity,
+            theme,
+            panel,
+            axis,
+            hud,
+            strokes,
+        }
+    }
+
+    /// Serialises the overlay as a standalone SVG document, optionally on top of
+    /// an image (e.g. a data URL of the video frame) stretched to the whole frame.
+    pub fn to_svg(&self, background: Option<&str>) -> String {
+        let (width, height) = self.size;
+        let mut buf = String::default();
+        let _ = write!(
+            buf,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height
+        );
+        if let Some(href) = background {
+            let _ = write!(
+                buf,
+                r#"<image href="{}" x="0" y="0" width="{}" height="{}" preserveAspectRatio="none"/>"#,
+                escape(href),
+                width,
+                height
+            );
+        }
+        let _ = write!(buf, r#"<g opacity="{}">"#, self.opacity);
+
+        if let Some(panel) = &self.panel {
+            let _ = write!(
+                buf,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}" fill="{}"/>"#,
+                panel.rect.x,
+                panel.rect.y,
+                panel.rect.width,
+                panel.rect.height,
+                panel.radius,
+                panel.fill
+            );
+        }
+
+        let Axis { from, to, ticks } = &self.axis;
+        let _ = write!(
+            buf,
+            r#"<g stroke="{}" stroke-width="1"><line x1="{}" y1="{}" x2="{}" y2="{}"/>"#,
+            self.theme.axis, from.0, from.1, to.0, to.1
+        );
+        for tick in ticks.iter() {
+            let _ = write!(
+                buf,
+                r#"<line x1="{x}" y1="{}" x2="{x}" y2="{}"/>"#,
+                from.1,
+                from.1 + TICK_LENGTH,
+                x = tick.x
+            );
+        }
+        let _ = write!(
+            buf,
+            r#"</g><g fill="{}" font-size="{}px" text-anchor="middle" font-family="sans-serif">"#,
+            self.theme.text, AXIS_LABEL_SIZE
+        );
+        for tick in ticks.iter() {
+            let _ = write!(
+                buf,
+                r#"<text x="{}" y="{}">{}</text>"#,
+                tick.x,
+                from.1 + TICK_LENGTH + AXIS_LABEL_SIZE,
+                escape(&tick.label)
+            );
+        }
+
+        let _ = write!(
+            buf,
+            r#"</g><g font-size="{}px" font-family="sans-serif">"#,
+            HUD_FONT_SIZE
+        );
+        for line in self.hud.lines.iter() {
+            let _ = write!(
+                buf,
+                r#"<text x="{}" y="{}" fill="{}">{}</text>"#,
+                line.position.0,
+                line.position.1,
+                self.theme.color(line.kind),
+                escape(&line.text)
+            );
+        }
+        buf.push_str("</g>");
+
+        for stroke in self.strokes.iter().filter(|stroke| !stroke.path.is_empty()) {
+            let _ = write!(
+                buf,
+                r#"<path d="{}" stroke="{}" stroke-width="{}" stroke-opacity="{}" stroke-linecap="round" stroke-linejoin="round" fill="none"/>"#,
+                stroke.path.to_svg().trim_end(),
+                stroke.color,
+                stroke.width,
+                stroke.opacity
+            );
+        }
+
+        buf.push_str("</g></svg>");
+        buf
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        models::{json::ShotDataJson, json::SHOT1, json::SHOT2, ShotData},
+        Alignment, Comparison, Placement,
+    };
+
+    fn overlay(settings: &Settings) -> Overlay {
+        let data: ShotData = serde_json::from_str::<ShotDataJson>(SHOT2).unwrap().into();
+        let units = Units::from_shot(&data);
+        let charts = ChartData::all_in(&data, &units);
+        let time_span = Range::from_series(&data.elapsed);
+        Overlay::build(&charts, &units, &time_span, 30.0, (1080., 1920.), settings)
+    }
+
+    #[test]
+    fn test_overlay_build_stays_in_block() {
+        let settings = Settings {
+            placement: Placement::TopRight,
+            ..Default::default()
+        };
+        let block = settings.placement.rect().scale((1080., 1920.));
+        let overlay = overlay(&settings);
+        assert_eq!(overlay.strokes.len(), SeriesKind::ALL.len());
+        for stroke in overlay.strokes.iter() {
+            for (x, y) in stroke.path.points.iter() {
+                assert!(block.x <= *x && *x <= block.x + block.width);
+                assert!(block.y <= *y && *y <= block.y + block.height);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlay_build_skips_disabled_series() {
+        let settings = Settings {
+            series: vec![SeriesKind::Pressure, SeriesKind::TempMix],
+            ..Default::default()
+        };
+        let overlay = overlay(&settings);
+        let theme = settings.theme.theme();
+        let colors: Vec<_> = overlay.strokes.iter().map(|stroke| stroke.color).collect();
+        assert_eq!(colors, vec![theme.pressure, theme.temp_mix]);
+        assert!(overlay
+            .hud
+            .lines
+            .iter()
+            .all(|line| settings.series.contains(&line.kind)));
+    }
+
+    #[test]
+    fn test_overlay_to_svg() {
+        let settings = Settings {
+            panel: true,
+            ..Default::default()
+        };
+        let svg = overlay(&settings).to_svg(Some("data:image/png;base64,AAAA"));
+        assert!(svg
+            .starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="1080" height="1920""#));
+        assert!(svg.contains(r#"<image href="data:image/png;base64,AAAA""#));
+        assert!(svg.contains("<rect "));
+        assert!(svg.contains("Pressure "));
+        assert_eq!(svg.matches("<path ").count(), SeriesKind::ALL.len());
+        assert!(svg.ends_with("</g></svg>"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape(r#"<a & "b">"#), "&lt;a &amp; &quot;b&quot;&gt;");
+    }
+
+    #[test]
+    fn test_overlay_build_compared() {
+        let shot =
+            |json| -> ShotData { serde_json::from_str::<ShotDataJson>(json).unwrap().into() };
+        let (data, reference) = (shot(SHOT2), shot(SHOT1));
+        let units = Units::from_shot(&data);
+        let comparison = Comparison::new(&data, &reference, &units, Alignment::FirstDrops);
+        let time_span = Range::from_series(&data.elapsed);
+        let settings = Settings {
+            placement: Placement::TopRight,
+            ..Default::default()
+        };
+        let size = (1080., 1920.);
+        let overlay = Overlay::build_compared(
+            &comparison.charts,
+            &comparison.reference,
+            &units,
+            &time_span,
+            30.0,
+            size,
+            &settings,
+        );
+
+        // Reference strokes come first so the shot is drawn over them.
+        let count = SeriesKind::ALL.len();
+        assert_eq!(overlay.strokes.len(), 2 * count);
+        assert!(overlay.strokes[..count]
+            .iter()
+            .all(|stroke| stroke.width < STROKE_WIDTH && stroke.opacity < 1.));
+        let block = settings.placement.rect().scale(size);
+        for stroke in overlay.strokes.iter() {
+            for (x, y) in stroke.path.points.iter() {
+                assert!(block.x <= *x && *x <= block.x + block.width);
+                assert!(block.y <= *y && *y <= block.y + block.height);
+            }
+        }
+        assert!(overlay.hud.lines[0].text.ends_with(')'));
+        assert!(overlay.to_svg(None).contains(r#"stroke-opacity="0.5""#));
+    }
+}
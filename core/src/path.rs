@@ -0,0 +1,153 @@
+use crate::{
+    models::{ChartData, DataPoint},
+    scale,
+};
+
+/// Polyline of a series in output coordinates, shared by every rendering backend.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+    pub points: Vec<(f32, f32)>,
+}
+
+impl Path {
+    /// Projects the samples of `data` up to `until` (seconds) into the given codomains.
+    pub fn build(
+        data: &ChartData,
+        time_domain: (f32, f32),
+        time_codomain: (f32, f32),
+        data_codomain: (f32, f32),
+        until: f32,
+    ) -> Self {
+        let x = scale(time_domain, time_codomain);
+        let y = scale(data.range.as_tuple(), data_codomain);
+
+        let mut points = vec![];
+        for (i, dp) in data.series.iter().enumerate() {
+            if 0 < i && until < dp.t() {
+                break;
+            }
+
+            if let DataPoint::Present((t, v)) = dp {
+                points.push((x(*t), y(*v)));
+            }
+        }
+
+        Self { points }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Closed polygon around the polyline, `width` thick, for outputs that can
+    /// only fill shapes. Joins are not mitred, which is fine for thin strokes.
+    pub fn outline(&self, width: f32) -> Vec<(f32, f32)> {
+        let points = &self.points;
+        if points.len() < 2 {
+            return vec![];
+        }
+
+        let half = width / 2.;
+        let mut normal = (0., 0.);
+        let normals: Vec<(f32, f32)> = (0..points.len())
+            .map(|i| {
+                let (x0, y0) = points[i.saturating_sub(1)];
+                let (x1, y1) = points[(i + 1).min(points.len() - 1)];
+                let (dx, dy) = (x1 - x0, y1 - y0);
+                let length = (dx * dx + dy * dy).sqrt();
+                if 0. < length {
+                    normal = (-dy / length * half, dx / length * half);
+                }
+                normal
+            })
+            .collect();
+
+        let left = points
+            .iter()
+            .zip(normals.iter())
+            .map(|((x, y), (nx, ny))| (x + nx, y + ny));
+        let right = points
+            .iter()
+            .zip(normals.iter())
+            .rev()
+            .map(|((x, y), (nx, ny))| (x - nx, y - ny));
+        left.chain(right).collect()
+    }
+
+    pub fn to_svg(&self) -> String {
+        let mut buf = String::default();
+        for (i, (x, y)) in self.points.iter().enumerate() {
+            let command = if i == 0 { 'M' } else { 'L' };
+            buf.push_str(format!("{}{} {} ", command, x, y).as_str());
+        }
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::Range;
+
+    fn data() -> ChartData {
+        ChartData {
+            series: vec![
+                DataPoint::Present((0.0, 0.0)),
+                DataPoint::Present((1.0, 5.0)),
+                DataPoint::NoData(2.0),
+                DataPoint::Present((3.0, 10.0)),
+                DataPoint::Present((4.0, 0.0)),
+            ],
+            range: Range {
+                min: 0.0,
+                max: 10.0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_path_build() {
+        let actual = Path::build(&data(), (0.0, 4.0), (0.0, 400.0), (100.0, 0.0), 3.0);
+        let expected = Path {
+            points: vec![(0.0, 100.0), (100.0, 50.0), (300.0, 0.0)],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_path_build_keeps_first_point() {
+        let actual = Path::build(&data(), (0.0, 4.0), (0.0, 400.0), (100.0, 0.0), -1.0);
+        let expected = Path {
+            points: vec![(0.0, 100.0)],
+        };
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_path_outline() {
+        let path = Path {
+            points: vec![(0.0, 10.0), (10.0, 10.0), (20.0, 10.0)],
+        };
+        assert_eq!(
+            path.outline(2.0),
+            vec![
+                (0.0, 11.0),
+                (10.0, 11.0),
+                (20.0, 11.0),
+                (20.0, 9.0),
+                (10.0, 9.0),
+                (0.0, 9.0)
+            ]
+        );
+        assert!(Path::default().outline(2.0).is_empty());
+    }
+
+    #[test]
+    fn test_path_to_svg() {
+        let path = Path {
+            points: vec![(0.0, 100.0), (100.0, 50.0), (300.0, 0.0)],
+        };
+        assert_eq!(path.to_svg(), "M0 100 L100 50 L300 0 ");
+    }
+}
@@ -0,0 +1,266 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    models::{json::ShotDataJson, ShotData},
+    Settings,
+};
+
+/// Schema version written by this build.
+pub const PROJECT_VERSION: u32 = 1;
+
+/// File name suffix of project files.
+pub const PROJECT_EXTENSION: &str = ".dvo.json";
+
+/// Upgrades a project of version `i + 1` to version `i + 2`. Fields added with a
+/// default don't need a step; renames, moves and changes of meaning do.
+const MIGRATIONS: [fn(&mut Value); PROJECT_VERSION as usize - 1] = [];
+
+/// Shot the overlay is drawn from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShotRef {
+    /// The shot file itself, as exported by the DE1 app.
+    Embedded(Value),
+    /// Path to the shot file, relative to the project file.
+    Path(String),
+    /// A shot with no file of its own, e.g. one from the library.
+    Parsed(Box<ShotData>),
+}
+
+/// Video the overlay was synced against. Videos are too large to embed, so the
+/// size and a fingerprint tell whether a file picked later is the same one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VideoRef {
+    pub name: String,
+    pub size: u64,
+    pub hash: String,
+}
+
+/// Note pinned to a moment of the shot.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    /// Seconds into the shot.
+    pub t: f32,
+    pub text: String,
+}
+
+/// Everything needed to reproduce an overlay, saved as a `.dvo.json` file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Project {
+    pub version: u32,
+    pub shot: ShotRef,
+    #[serde(default)]
+    pub video: Option<VideoRef>,
+    /// Sync offset, trim, layout, theme and units.
+    #[serde(default)]
+    pub settings: Settings,
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+}
+
+#[derive(Debug)]
+pub enum ProjectError {
+    Json(serde_json::Error),
+    MissingVersion,
+    /// Written by a newer build than this one.
+    TooNew(u32),
+    /// The embedded shot has nothing to draw.
+    NoSamples,
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "invalid project file: {}", e),
+            Self::MissingVersion => write!(f, "not a project file: missing version"),
+            Self::TooNew(version) => write!(
+                f,
+                "project version {} is newer than the supported version {}",
+                version, PROJECT_VERSION
+            ),
+            Self::NoSamples => write!(f, "invalid project file: the shot has no samples"),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ProjectError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// Brings a project of any earlier version up to [`PROJECT_VERSION`].
+fn migrate(mut value: Value) -> Result<Value, ProjectError> {
+    let version = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .filter(|version| 0 < *version)
+        .ok_or(ProjectError::MissingVersion)? as u32;
+    if PROJECT_VERSION < version {
+        return Err(ProjectError::TooNew(version));
+    }
+
+    for migration in MIGRATIONS.iter().skip(version as usize - 1) {
+        migration(&mut value);
+    }
+    value["version"] = PROJECT_VERSION.into();
+    Ok(value)
+}
+
+impl Project {
+    pub fn new(shot: ShotRef, settings: Settings) -> Self {
+        Self {
+            version: PROJECT_VERSION,
+            shot,
+            video: None,
+            settings,
+            annotations: vec![],
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ProjectError> {
+        let value = migrate(serde_json::from_str(json)?)?;
+        let project: Self = serde_json::from_value(value)?;
+        if let Some(data) = project.embedded_shot() {
+            if data?.elapsed.is_empty() {
+                return Err(ProjectError::NoSamples);
+            }
+        }
+        Ok(project)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("project is always serialisable")
+    }
+
+    /// The embedded shot, or `None` when the project only has its path.
+    pub fn embedded_shot(&self) -> Option<Result<ShotData, serde_json::Error>> {
+        match &self.shot {
+            ShotRef::Embedded(value) => {
+                Some(serde_json::from_value::<ShotDataJson>(value.clone()).map(ShotData::from))
+            }
+            ShotRef::Parsed(data) => Some(Ok((**data).clone())),
+            ShotRef::Path(_) => None,
+        }
+    }
+}
+
+/// Quick fingerprint of a file from its size and its first and last bytes
+/// (FNV-1a, 64 bits). Not cryptographic; only meant to catch a different video.
+pub fn fingerprint(size: u64, head: &[u8], tail: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in size.to_le_bytes().iter().chain(head).chain(tail) {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Bytes read from each end of a file for [`fingerprint`].
+pub const FINGERPRINT_CHUNK: u64 = 1 << 20;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{models::json::SHOT2, Placement, ThemePreset, Units};
+
+    fn project() -> Project {
+        let settings = Settings {
+            placement: Placement::TopRight,
+            theme: ThemePreset::ColorBlind,
+            units: Some(Units::IMPERIAL),
+            offset: 3.5,
+            ..Default::default()
+        };
+        let mut project = Project::new(
+            ShotRef::Embedded(serde_json::from_str(SHOT2).unwrap()),
+            settings,
+        );
+        project.video = Some(VideoRef {
+            name: "shot2.mp4".into(),
+            size: 1234,
+            hash: fingerprint(1234, b"head", b"tail"),
+        });
+        project.annotations.push(Annotation {
+            t: 12.5,
+            text: "Channeling".into(),
+        });
+        project
+    }
+
+    #[test]
+    fn test_project_round_trip() {
+        let project = project();
+        let actual = Project::from_json(&project.to_json()).unwrap();
+        assert_eq!(actual, project);
+    }
+
+    #[test]
+    fn test_project_embedded_shot() {
+        let data = project().embedded_shot().unwrap().unwrap();
+        assert!(!data.elapsed.is_empty());
+
+        let mut project = Project::new(ShotRef::Path("shot.json".into()), Settings::default());
+        assert!(project.embedded_shot().is_none());
+
+        project.shot = ShotRef::Parsed(Box::new(data.clone()));
+        let project = Project::from_json(&project.to_json()).unwrap();
+        assert_eq!(project.embedded_shot().unwrap().unwrap(), data);
+    }
+
+    #[test]
+    fn test_project_defaults_missing_fields() {
+        let json =
+            r#"{ "version": 1, "shot": { "path": "shot.json" }, "settings": { "offset": 2.0 } }"#;
+        let project = Project::from_json(json).unwrap();
+        assert_eq!(project.shot, ShotRef::Path("shot.json".into()));
+        assert_eq!(project.video, None);
+        assert_eq!(project.settings.offset, 2.0);
+        assert_eq!(project.settings.opacity, Settings::default().opacity);
+        assert!(project.annotations.is_empty());
+    }
+
+    #[test]
+    fn test_project_rejects_unknown_versions() {
+        let json = r#"{ "shot": { "path": "shot.json" } }"#;
+        assert!(matches!(
+            Project::from_json(json),
+            Err(ProjectError::MissingVersion)
+        ));
+
+        let json = r#"{ "version": 99, "shot": { "path": "shot.json" } }"#;
+        assert!(matches!(
+            Project::from_json(json),
+            Err(ProjectError::TooNew(99))
+        ));
+    }
+
+    #[test]
+    fn test_project_rejects_empty_shots() {
+        let mut project = project();
+        let ShotRef::Embedded(raw) = &mut project.shot else {
+            unreachable!()
+        };
+        raw["elapsed"] = serde_json::json!([]);
+        assert!(matches!(
+            Project::from_json(&project.to_json()),
+            Err(ProjectError::NoSamples)
+        ));
+
+        project.shot = ShotRef::Parsed(Box::default());
+        assert!(matches!(
+            Project::from_json(&project.to_json()),
+            Err(ProjectError::NoSamples)
+        ));
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint(0, b"", b""), "a8c7f832281a39c5");
+        assert_ne!(fingerprint(4, b"ab", b"cd"), fingerprint(4, b"ab", b"ce"));
+    }
+}
@@ -5,7 +5,7 @@ pub struct Range {
 }
 
 impl Range {
-    pub fn from_series(series: &Vec<f32>) -> Self {
+    pub fn from_series(series: &[f32]) -> Self {
         if series.is_empty() {
             panic!("Requires non-empty vector");
         }
@@ -31,6 +31,14 @@ impl Range {
         }
     }
 
+    /// Smallest range covering both.
+    pub fn union(&self, other: &Range) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
     pub fn as_tuple(&self) -> (f32, f32) {
         (self.min, self.max)
     }
@@ -51,10 +59,27 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
     fn test_range_from_series_zero() {
         let series = vec![0.0, 0.0];
         let actual = Range::from_series(&series);
         let expected = Range { min: 0.0, max: 0.0 };
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_range_union() {
+        let a = Range { min: 0.0, max: 2.0 };
+        let b = Range {
+            min: -1.0,
+            max: 1.0,
+        };
+        assert_eq!(
+            a.union(&b),
+            Range {
+                min: -1.0,
+                max: 2.0
+            }
+        );
+    }
 }
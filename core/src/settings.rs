@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{models::SeriesKind, Placement, ThemePreset, Units};
+
+/// Missing fields fall back to their defaults, so saved settings keep loading
+/// when new ones are added.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub backend: Backend,
+    pub placement: Placement,
+    pub opacity: f32,
+    pub panel: bool,
+    pub theme: ThemePreset,
+    /// Series drawn on the overlay.
+    pub series: Vec<SeriesKind>,
+    /// Display units, or `None` to follow the shot's own app settings.
+    pub units: Option<Units>,
+    /// Seconds into the video at which the shot starts.
+    pub offset: f32,
+    pub trim: Trim,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            backend: Backend::default(),
+            placement: Placement::default(),
+            opacity: 1.0,
+            panel: false,
+            theme: ThemePreset::default(),
+            series: SeriesKind::ALL.to_vec(),
+            units: None,
+            offset: 0.0,
+            trim: Trim::default(),
+        }
+    }
+}
+
+/// Footage kept around the shot when cutting the video, in seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Trim {
+    pub pre_roll: f32,
+    pub post_roll: f32,
+}
+
+impl Default for Trim {
+    fn default() -> Self {
+        Self {
+            pre_roll: 2.0,
+            post_roll: 2.0,
+        }
+    }
+}
+
+/// How the overlay is drawn on top of the video.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum Backend {
+    #[default]
+    Svg,
+    Canvas,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 2] = [Backend::Svg, Backend::Canvas];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Svg => "SVG",
+            Self::Canvas => "Canvas",
+        }
+    }
+}
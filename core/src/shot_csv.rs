@@ -0,0 +1,679 @@
+use std::collections::HashMap;
+
+use crate::models::{ProfileSection, ShotData};
+
+/// Time series a CSV column can be read into, in the metric units of [`ShotData`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Elapsed,
+    Pressure,
+    PressureGoal,
+    Flow,
+    FlowGoal,
+    FlowByWeight,
+    FlowByWeightRaw,
+    TempBasket,
+    TempMix,
+    TempGoal,
+    Weight,
+    WaterDispensed,
+    Resistance,
+    ResistanceByWeight,
+    StateChange,
+}
+
+impl Column {
+    pub const ALL: [Column; 15] = [
+        Column::Elapsed,
+        Column::Pressure,
+        Column::PressureGoal,
+        Column::Flow,
+        Column::FlowGoal,
+        Column::FlowByWeight,
+        Column::FlowByWeightRaw,
+        Column::TempBasket,
+        Column::TempMix,
+        Column::TempGoal,
+        Column::Weight,
+        Column::WaterDispensed,
+        Column::Resistance,
+        Column::ResistanceByWeight,
+        Column::StateChange,
+    ];
+
+    /// Header written on export.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Elapsed => "elapsed",
+            Self::Pressure => "pressure",
+            Self::PressureGoal => "pressure_goal",
+            Self::Flow => "flow",
+            Self::FlowGoal => "flow_goal",
+            Self::FlowByWeight => "flow_by_weight",
+            Self::FlowByWeightRaw => "flow_by_weight_raw",
+            Self::TempBasket => "temperature_basket",
+            Self::TempMix => "temperature_mix",
+            Self::TempGoal => "temperature_goal",
+            Self::Weight => "weight",
+            Self::WaterDispensed => "water_dispensed",
+            Self::Resistance => "resistance",
+            Self::ResistanceByWeight => "resistance_by_weight",
+            Self::StateChange => "state_change",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Elapsed => "Time (s)",
+            Self::Pressure => "Pressure (bar)",
+            Self::PressureGoal => "Pressure goal (bar)",
+            Self::Flow => "Flow (ml/s)",
+            Self::FlowGoal => "Flow goal (ml/s)",
+            Self::FlowByWeight => "Flow by weight (g/s)",
+            Self::FlowByWeightRaw => "Raw flow by weight (g/s)",
+            Self::TempBasket => "Basket temperature (°C)",
+            Self::TempMix => "Mix temperature (°C)",
+            Self::TempGoal => "Temperature goal (°C)",
+            Self::Weight => "Weight (g)",
+            Self::WaterDispensed => "Water dispensed (10 ml)",
+            Self::Resistance => "Resistance",
+            Self::ResistanceByWeight => "Resistance by weight",
+            Self::StateChange => "State change",
+        }
+    }
+
+    /// Other headers the column is known by, e.g. in Gaggiuino or scale app logs,
+    /// normalised by [`normalise`].
+    fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            Self::Elapsed => &[
+                "time",
+                "t",
+                "seconds",
+                "timems",
+                "elapsedms",
+                "timeinshot",
+                "elapsedtime",
+            ],
+            Self::Pressure => &["bar", "pressurebar"],
+            Self::PressureGoal => &["targetpressure", "setpressure"],
+            Self::Flow => &["pumpflow", "flowrate"],
+            Self::FlowGoal => &["targetflow", "targetpumpflow"],
+            Self::FlowByWeight => &["weightflow", "shotweightflow", "flowweight"],
+            Self::TempBasket => &["temperature", "temp"],
+            Self::TempGoal => &["targettemperature", "targettemp"],
+            Self::Weight => &["shotweight", "scale", "grams", "mass"],
+            _ => &[],
+        }
+    }
+
+    pub fn values<'a>(&self, data: &'a ShotData) -> &'a [f32] {
+        match self {
+            Self::Elapsed => &data.elapsed,
+            Self::Pressure => &data.pressure.pressure,
+            Self::PressureGoal => &data.pressure.goal,
+            Self::Flow => &data.flow.flow,
+            Self::FlowGoal => &data.flow.goal,
+            Self::FlowByWeight => &data.flow.by_weight,
+            Self::FlowByWeightRaw => &data.flow.by_weight_raw,
+            Self::TempBasket => &data.temperature.basket,
+            Self::TempMix => &data.temperature.mix,
+            Self::TempGoal => &data.temperature.goal,
+            Self::Weight => &data.totals.weight,
+            Self::WaterDispensed => &data.totals.water_dispensed,
+            Self::Resistance => &data.resistance.resistance,
+            Self::ResistanceByWeight => &data.resistance.by_weight,
+            Self::StateChange => &data.state_change,
+        }
+    }
+
+    fn values_mut<'a>(&self, data: &'a mut ShotData) -> &'a mut Vec<f32> {
+        match self {
+            Self::Elapsed => &mut data.elapsed,
+            Self::Pressure => &mut data.pressure.pressure,
+            Self::PressureGoal => &mut data.pressure.goal,
+            Self::Flow => &mut data.flow.flow,
+            Self::FlowGoal => &mut data.flow.goal,
+            Self::FlowByWeight => &mut data.flow.by_weight,
+            Self::FlowByWeightRaw => &mut data.flow.by_weight_raw,
+            Self::TempBasket => &mut data.temperature.basket,
+            Self::TempMix => &mut data.temperature.mix,
+            Self::TempGoal => &mut data.temperature.goal,
+            Self::Weight => &mut data.totals.weight,
+            Self::WaterDispensed => &mut data.totals.water_dispensed,
+            Self::Resistance => &mut data.resistance.resistance,
+            Self::ResistanceByWeight => &mut data.resistance.by_weight,
+            Self::StateChange => &mut data.state_change,
+        }
+    }
+}
+
+/// Lowercase letters and digits only, so that `Pressure (bar)` matches `pressure_bar`.
+fn normalise(header: &str) -> String {
+    header
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CsvLayout {
+    /// One row per sample and one column per series.
+    #[default]
+    Tidy,
+    /// One row per sample and series, with `series` and `value` columns.
+    Long,
+}
+
+impl CsvLayout {
+    pub const ALL: [CsvLayout; 2] = [CsvLayout::Tidy, CsvLayout::Long];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Tidy => "Tidy (a column per series)",
+            Self::Long => "Long (a row per value)",
+        }
+    }
+}
+
+/// Size of the `state_change` marker, whose sign the DE1 flips at every step.
+const STEP_MARKER: f32 = 10_000_000.;
+
+const PHASE: &str = "phase";
+const STEP: &str = "step";
+const SERIES: &str = "series";
+const VALUE: &str = "value";
+
+fn cell(value: Option<&f32>) -> String {
+    match value {
+        Some(value) if value.is_finite() => value.to_string(),
+        _ => String::default(),
+    }
+}
+
+/// Writes the time series of a shot, with the index and name of the profile
+/// step each sample was recorded in. Series the shot lacks are left out and
+/// missing samples are empty cells.
+pub fn to_csv(data: &ShotData, layout: CsvLayout) -> String {
+    let phases = data.phases();
+    let series: Vec<Column> = Column::ALL[1..]
+        .iter()
+        .copied()
+        .filter(|column| !column.values(data).is_empty())
+        .collect();
+
+    let mut writer = csv::Writer::from_writer(vec![]);
+    let mut write = |record: Vec<String>| {
+        writer
+            .write_record(record)
+            .expect("writing to memory can't fail")
+    };
+    let mut header = vec![Column::Elapsed.name(), PHASE, STEP];
+    match layout {
+        CsvLayout::Tidy => header.extend(series.iter().map(Column::name)),
+        CsvLayout::Long => header.extend([SERIES, VALUE]),
+    }
+    write(header.into_iter().map(String::from).collect());
+
+    for (i, t) in data.elapsed.iter().enumerate() {
+        let phase = phases.get(i);
+        let step = phase.and_then(|phase| data.profile.steps.get(*phase));
+        let row = vec![
+            t.to_string(),
+            phase.map(ToString::to_string).unwrap_or_default(),
+            step.cloned().unwrap_or_default(),
+        ];
+        match layout {
+            CsvLayout::Tidy => {
+                let mut row = row;
+                row.extend(series.iter().map(|column| cell(column.values(data).get(i))));
+                write(row);
+            }
+            CsvLayout::Long => {
+                for column in series.iter() {
+                    let value = cell(column.values(data).get(i));
+                    if !value.is_empty() {
+                        let mut row = row.clone();
+                        row.extend([column.name().to_string(), value]);
+                        write(row);
+                    }
+                }
+            }
+        }
+    }
+
+    let bytes = writer.into_inner().expect("writing to memory can't fail");
+    String::from_utf8(bytes).expect("records are UTF-8")
+}
+
+/// Unit of the time column of an imported CSV.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeUnit {
+    #[default]
+    Seconds,
+    Milliseconds,
+}
+
+impl TimeUnit {
+    pub const ALL: [TimeUnit; 2] = [TimeUnit::Seconds, TimeUnit::Milliseconds];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Seconds => "Seconds",
+            Self::Milliseconds => "Milliseconds",
+        }
+    }
+
+    fn seconds(&self, value: f32) -> f32 {
+        match self {
+            Self::Seconds => value,
+            Self::Milliseconds => value / 1000.,
+        }
+    }
+}
+
+/// Cells of a CSV file, read before the user tells which column holds what.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Reads a CSV file with a header row. Files in the long layout written by
+/// [`to_csv`] are turned back into one column per series.
+pub fn read_csv(text: &str) -> Result<CsvTable, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .map_err(|e| e.to_string())?
+        .iter()
+        .map(String::from)
+        .collect();
+    let rows = reader
+        .records()
+        .map(|record| {
+            record
+                .map(|record| record.iter().map(String::from).collect())
+                .map_err(|e| e.to_string())
+        })
+        .collect::<Result<Vec<Vec<String>>, String>>()?;
+    if headers.is_empty() || rows.is_empty() {
+        return Err("the file has no rows".into());
+    }
+
+    let table = CsvTable { headers, rows };
+    match (
+        table.position(Column::Elapsed.name()),
+        table.position(SERIES),
+        table.position(VALUE),
+    ) {
+        (Some(time), Some(series), Some(value)) => Ok(table.widen(time, series, value)),
+        _ => Ok(table),
+    }
+}
+
+impl CsvTable {
+    fn position(&self, header: &str) -> Option<usize> {
+        self.headers.iter().position(|other| other == header)
+    }
+
+    /// Pivots a long table into one row per time and one column per series,
+    /// sorted by time. The rows may come in any order, e.g. series by series
+    /// as `pandas.DataFrame.melt` writes them. Other columns, such as the step,
+    /// are kept from the first row of each time.
+    fn widen(&self, time: usize, series: usize, value: usize) -> Self {
+        let ids: Vec<usize> = std::iter::once(time)
+            .chain((0..self.headers.len()).filter(|i| ![time, series, value].contains(i)))
+            .collect();
+        let mut headers: Vec<String> = ids.iter().map(|i| self.headers[*i].clone()).collect();
+        let mut rows: Vec<Vec<String>> = vec![];
+        let mut by_time: HashMap<&str, usize> = HashMap::new();
+        for row in self.rows.iter() {
+            let column = match headers.iter().position(|header| *header == row[series]) {
+                Some(column) => column,
+                None => {
+                    headers.push(row[series].clone());
+                    headers.len() - 1
+                }
+            };
+            let index = *by_time.entry(&row[time]).or_insert_with(|| {
+                rows.push(ids.iter().map(|i| row[*i].clone()).collect());
+                rows.len() - 1
+            });
+            let widened = &mut rows[index];
+            widened.resize(widened.len().max(column + 1), String::default());
+            widened[column] = row[value].clone();
+        }
+        for row in rows.iter_mut() {
+            row.resize(headers.len(), String::default());
+        }
+        // Unreadable times go last, to be reported on import.
+        let t = |row: &Vec<String>| row[0].parse::<f64>().unwrap_or(f64::INFINITY);
+        rows.sort_by(|a, b| t(a).total_cmp(&t(b)));
+        Self { headers, rows }
+    }
+
+    /// Names the steps of the profile after the `phase` and `step` columns and,
+    /// unless a column holds them, marks the samples where each step starts.
+    fn read_steps(&self, data: &mut ShotData, mapping: &[Option<Column>]) {
+        let Some(phase) = self.position(PHASE) else {
+            return;
+        };
+        // A phase index can't exceed the number of samples.
+        let phases: Vec<Option<usize>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                row[phase]
+                    .parse()
+                    .ok()
+                    .filter(|phase| *phase < self.rows.len())
+            })
+            .collect();
+        if phases.iter().all(Option::is_none) {
+            return;
+        }
+
+        if !mapping.contains(&Some(Column::StateChange)) {
+            data.state_change = phases
+                .iter()
+                .map(|phase| match phase {
+                    Some(phase) if phase % 2 == 0 => STEP_MARKER,
+                    Some(_) => -STEP_MARKER,
+                    None => 0.,
+                })
+                .collect();
+        }
+        if let Some(step) = self.position(STEP) {
+            let steps = &mut data.profile.steps;
+            for (row, phase) in self.rows.iter().zip(phases) {
+                let Some(phase) = phase else {
+                    continue;
+                };
+                if steps.len() <= phase {
+                    steps.resize(phase + 1, String::default());
+                }
+                if steps[phase].is_empty() {
+                    steps[phase] = row[step].clone();
+                }
+            }
+        }
+    }
+
+    /// Best guess of the series held by each column, from its header. A series
+    /// is only guessed for its first column.
+    pub fn guess_mapping(&self) -> Vec<Option<Column>> {
+        let mut mapping: Vec<Option<Column>> = vec![];
+        for header in self.headers.iter() {
+            let header = normalise(header);
+            let column = Column::ALL.into_iter().find(|column| {
+                !mapping.contains(&Some(*column))
+                    && (normalise(column.name()) == header
+                        || normalise(column.label()) == header
+                        || column.aliases().contains(&header.as_str()))
+            });
+            mapping.push(column);
+        }
+        mapping
+    }
+
+    /// Milliseconds when the header of the time column says so, e.g. `Time (ms)`.
+    pub fn guess_time_unit(&self, mapping: &[Option<Column>]) -> TimeUnit {
+        let header = mapping
+            .iter()
+            .position(|column| *column == Some(Column::Elapsed))
+            .map(|i| normalise(&self.headers[i]));
+        match header {
+            Some(header) if header.ends_with("ms") || header.ends_with("millis") => {
+                TimeUnit::Milliseconds
+            }
+            _ => TimeUnit::Seconds,
+        }
+    }
+
+    /// Builds a shot out of the columns mapped to a series, one per entry of
+    /// `mapping`. Time is shifted to start at 0 and empty cells are gaps. A
+    /// series can only be read from one column. The `phase` and `step` columns
+    /// written by [`to_csv`] give back the steps of the profile.
+    pub fn to_shot(
+        &self,
+        title: &str,
+        mapping: &[Option<Column>],
+        time_unit: TimeUnit,
+    ) -> Result<ShotData, String> {
+        if !mapping.contains(&Some(Column::Elapsed)) {
+            return Err("no column is mapped to the time".into());
+        }
+        for (i, column) in mapping.iter().enumerate() {
+            if let Some(column) = column {
+                if mapping[..i].contains(&Some(*column)) {
+                    return Err(format!(
+                        "more than one column is mapped to {}",
+                        column.label()
+                    ));
+                }
+            }
+        }
+
+        let mut data = ShotData {
+            version: "csv".into(),
+            profile: ProfileSection {
+                title: title.into(),
+                steps: vec![],
+            },
+            ..Default::default()
+        };
+        for (i, row) in self.rows.iter().enumerate() {
+            for (header, (cell, column)) in self.headers.iter().zip(row.iter().zip(mapping)) {
+                let Some(column) = column else {
+                    continue;
+                };
+                let value = if cell.is_empty() && *column != Column::Elapsed {
+                    f32::NAN
+                } else {
+                    cell.parse().map_err(|_| {
+                        format!("row {}, {}: invalid number \"{}\"", i + 2, header, cell)
+                    })?
+                };
+                column.values_mut(&mut data).push(value);
+            }
+        }
+
+        self.read_steps(&mut data, mapping);
+
+        let start = data.elapsed.first().copied().unwrap_or_default();
+        for t in data.elapsed.iter_mut() {
+            *t = time_unit.seconds(*t - start);
+        }
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::{json::ShotDataJson, json::SHOT2, PressureSection, TotalsSection};
+
+    fn shot() -> ShotData {
+        ShotData {
+            elapsed: vec![0.0, 0.5, 1.0],
+            pressure: PressureSection {
+                pressure: vec![0.0, 2.5, f32::NAN],
+                goal: vec![],
+            },
+            totals: TotalsSection {
+                weight: vec![0.0, 0.1, 1.2],
+                water_dispensed: vec![],
+            },
+            state_change: vec![0.0, 1e7, -1e7],
+            profile: ProfileSection {
+                title: "Default".into(),
+                steps: vec!["preinfusion".into(), "pour".into()],
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_to_csv_tidy() {
+        assert_eq!(
+            to_csv(&shot(), CsvLayout::Tidy),
+            "elapsed,phase,step,pressure,weight,state_change\n\
+             0,0,preinfusion,0,0,0\n\
+             0.5,0,preinfusion,2.5,0.1,10000000\n\
+             1,1,pour,,1.2,-10000000\n"
+        );
+    }
+
+    #[test]
+    fn test_to_csv_long() {
+        let csv = to_csv(&shot(), CsvLayout::Long);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "elapsed,phase,step,series,value");
+        assert_eq!(lines[1], "0,0,preinfusion,pressure,0");
+        assert_eq!(lines[6], "0.5,0,preinfusion,state_change,10000000");
+        assert_eq!(lines[7], "1,1,pour,weight,1.2");
+        assert_eq!(lines.len(), 1 + 3 * 3 - 1);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data: ShotData = serde_json::from_str::<ShotDataJson>(SHOT2).unwrap().into();
+        for layout in CsvLayout::ALL {
+            let table = read_csv(&to_csv(&data, layout)).unwrap();
+            let mapping = table.guess_mapping();
+            let actual = table.to_shot("shot2", &mapping, TimeUnit::Seconds).unwrap();
+            for column in Column::ALL {
+                assert_eq!(column.values(&actual), column.values(&data), "{:?}", column);
+            }
+            let steps = |data: &ShotData| -> Vec<Option<String>> {
+                data.elapsed
+                    .iter()
+                    .map(|t| data.phase_at(*t).map(String::from))
+                    .collect()
+            };
+            assert_eq!(actual.profile.steps, data.profile.steps, "{:?}", layout);
+            assert_eq!(steps(&actual), steps(&data), "{:?}", layout);
+        }
+    }
+
+    #[test]
+    fn test_to_shot_reads_steps() {
+        for layout in CsvLayout::ALL {
+            let table = read_csv(&to_csv(&shot(), layout)).unwrap();
+            // Without the markers, the steps are told apart by the phase.
+            let mapping: Vec<_> = table
+                .guess_mapping()
+                .into_iter()
+                .map(|column| column.filter(|column| *column != Column::StateChange))
+                .collect();
+            let data = table.to_shot("", &mapping, TimeUnit::Seconds).unwrap();
+            assert_eq!(data.profile.steps, ["preinfusion", "pour"]);
+            assert_eq!(data.phases(), [0, 0, 1]);
+        }
+    }
+
+    #[test]
+    fn test_read_csv_long_by_series() {
+        let table = read_csv(
+            "elapsed,series,value\n\
+             0,pressure,1\n\
+             0.5,pressure,2\n\
+             1,pressure,3\n\
+             1,weight,0.4\n\
+             0,weight,0\n",
+        )
+        .unwrap();
+        assert_eq!(table.headers, ["elapsed", "pressure", "weight"]);
+        assert_eq!(
+            table.rows,
+            [["0", "1", "0"], ["0.5", "2", ""], ["1", "3", "0.4"]]
+        );
+    }
+
+    #[test]
+    fn test_guess_mapping() {
+        let table = read_csv(
+            "Time (ms),Pressure (bar),pumpFlow,shotWeight,Temperature,targetPressure,Notes\n\
+             1000,0.1,0,0,92.5,9,\n",
+        )
+        .unwrap();
+        let mapping = table.guess_mapping();
+        assert_eq!(
+            mapping,
+            [
+                Some(Column::Elapsed),
+                Some(Column::Pressure),
+                Some(Column::Flow),
+                Some(Column::Weight),
+                Some(Column::TempBasket),
+                Some(Column::PressureGoal),
+                None,
+            ]
+        );
+        assert_eq!(table.guess_time_unit(&mapping), TimeUnit::Milliseconds);
+        assert_eq!(table.guess_time_unit(&[None; 7]), TimeUnit::Seconds);
+    }
+
+    #[test]
+    fn test_to_shot() {
+        let table = read_csv(
+            "timeInShot,pressure,weight,comment\n\
+             1500,0.5,,start\n\
+             2000,1.5,0.2,\n\
+             2500,3.0,1.0,\"9 bar, finally\"\n",
+        )
+        .unwrap();
+        let mapping = table.guess_mapping();
+        assert_eq!(
+            mapping,
+            [
+                Some(Column::Elapsed),
+                Some(Column::Pressure),
+                Some(Column::Weight),
+                None
+            ]
+        );
+        let data = table
+            .to_shot("Gaggiuino", &mapping, TimeUnit::Milliseconds)
+            .unwrap();
+        assert_eq!(data.profile.title, "Gaggiuino");
+        assert_eq!(data.elapsed, [0.0, 0.5, 1.0]);
+        assert_eq!(data.pressure.pressure, [0.5, 1.5, 3.0]);
+        assert!(data.totals.weight[0].is_nan());
+        assert_eq!(data.totals.weight[1..], [0.2, 1.0]);
+
+        assert_eq!(
+            table
+                .to_shot("", &[None; 4], TimeUnit::Seconds)
+                .unwrap_err(),
+            "no column is mapped to the time"
+        );
+        let mapping = [Some(Column::Elapsed), None, None, Some(Column::Flow)];
+        assert_eq!(
+            table.to_shot("", &mapping, TimeUnit::Seconds).unwrap_err(),
+            "row 2, comment: invalid number \"start\""
+        );
+        let mapping = [
+            Some(Column::Elapsed),
+            Some(Column::Pressure),
+            Some(Column::Pressure),
+            None,
+        ];
+        assert_eq!(
+            table.to_shot("", &mapping, TimeUnit::Seconds).unwrap_err(),
+            "more than one column is mapped to Pressure (bar)"
+        );
+    }
+
+    #[test]
+    fn test_read_csv_errors() {
+        assert!(read_csv("").is_err());
+        assert!(read_csv("time,pressure\n").is_err());
+        assert!(read_csv("time,pressure\n0,1,2\n").is_err());
+    }
+}
@@ -0,0 +1,287 @@
+//! Metrics computed from the samples of a shot, in the metric units of [`ShotData`].
+
+use crate::models::ShotData;
+
+/// Pressure (bar) that ends preinfusion.
+pub const PREINFUSION_PRESSURE: f32 = 4.0;
+
+/// Weight (g) in the cup that counts as the first drops.
+pub const FIRST_DROPS_WEIGHT: f32 = 0.5;
+
+/// The DE1 records water dispensed in tens of millilitres.
+const WATER_SCALE: f32 = 10.0;
+
+/// Peak and mean of a series over some samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+    pub peak: f32,
+    pub mean: f32,
+}
+
+impl Summary {
+    /// `None` when no value is a number.
+    fn of(values: impl Iterator<Item = f32>) -> Option<Self> {
+        let values: Vec<f32> = values.filter(|v| v.is_finite()).collect();
+        if values.is_empty() {
+            return None;
+        }
+        Some(Self {
+            peak: values.iter().copied().fold(f32::MIN, f32::max),
+            mean: values.iter().sum::<f32>() / values.len() as f32,
+        })
+    }
+}
+
+/// One step of the profile as it ran.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhaseStats {
+    /// Name of the step, when the profile names it.
+    pub name: Option<String>,
+    /// Seconds into the shot.
+    pub start: f32,
+    pub duration: f32,
+    pub pressure: Option<Summary>,
+    pub flow: Option<Summary>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShotStats {
+    pub total_time: f32,
+    /// Time until the pressure first reaches [`PREINFUSION_PRESSURE`].
+    pub preinfusion_time: Option<f32>,
+    /// Time until the weight first exceeds [`FIRST_DROPS_WEIGHT`].
+    pub first_drops_time: Option<f32>,
+    pub phases: Vec<PhaseStats>,
+    /// Last weight the scale recorded.
+    pub final_weight: Option<f32>,
+    /// Beverage out per ground coffee in, from the logged values or else the scale.
+    pub brew_ratio: Option<f32>,
+    /// Mean distance of the basket temperature from its goal.
+    pub temperature_deviation: Option<f32>,
+    /// Millilitres of water the machine pushed through the puck.
+    pub water_dispensed: Option<f32>,
+    /// Water that stayed in the puck, i.e. dispensed but not in the cup.
+    pub puck_retention: Option<f32>,
+}
+
+/// Last weight the scale recorded, if it recorded any.
+pub fn final_weight(data: &ShotData) -> Option<f32> {
+    data.totals
+        .weight
+        .iter()
+        .rev()
+        .find(|w| w.is_finite())
+        .copied()
+        .filter(|w| 0. < *w)
+}
+
+fn time_when(data: &ShotData, values: &[f32], f: impl Fn(f32) -> bool) -> Option<f32> {
+    let i = values.iter().position(|v| f(*v))?;
+    data.elapsed.get(i).copied()
+}
+
+fn phases(data: &ShotData) -> Vec<PhaseStats> {
+    let elapsed = &data.elapsed;
+    // Without a step marker per sample, the whole shot is one phase.
+    let indices = if data.state_change.len() == elapsed.len() {
+        data.phases()
+    } else {
+        vec![0; elapsed.len()]
+    };
+
+    let mut phases = vec![];
+    let mut start = 0;
+    while start < elapsed.len() {
+        let phase = indices[start];
+        let end = (start..elapsed.len())
+            .find(|i| indices[*i] != phase)
+            .unwrap_or(elapsed.len());
+        let until = elapsed.get(end).unwrap_or(&elapsed[end - 1]);
+        let series =
+            |values: &[f32]| Summary::of((start..end).filter_map(|i| values.get(i).copied()));
+        phases.push(PhaseStats {
+            name: data.profile.steps.get(phase).cloned(),
+            start: elapsed[start],
+            duration: until - elapsed[start],
+            pressure: series(&data.pressure.pressure),
+            flow: series(&data.flow.flow),
+        });
+        start = end;
+    }
+    phases
+}
+
+/// Water dispensed as recorded, or else the flow integrated over time.
+fn water_dispensed(data: &ShotData) -> Option<f32> {
+    let recorded = data
+        .totals
+        .water_dispensed
+        .iter()
+        .rev()
+        .find(|v| v.is_finite());
+    if let Some(recorded) = recorded {
+        return Some(recorded * WATER_SCALE);
+    }
+    if data.flow.flow.len() != data.elapsed.len() {
+        return None;
+    }
+    let samples: Vec<(f32, f32)> = data
+        .elapsed
+        .iter()
+        .zip(data.flow.flow.iter())
+        .filter(|(_, flow)| flow.is_finite())
+        .map(|(t, flow)| (*t, *flow))
+        .collect();
+    let water = samples
+        .windows(2)
+        .map(|pair| (pair[1].0 - pair[0].0) * (pair[0].1 + pair[1].1) / 2.)
+        .sum();
+    (1 < samples.len()).then_some(water)
+}
+
+impl ShotStats {
+    pub fn of(data: &ShotData) -> Self {
+        let meta = &data.meta;
+        let final_weight = final_weight(data);
+        let beverage = Some(meta.beverage)
+            .filter(|beverage| 0. < *beverage)
+            .or(final_weight);
+        let brew_ratio = beverage
+            .filter(|_| 0. < meta.dose)
+            .map(|beverage| beverage / meta.dose);
+
+        let temperature = &data.temperature;
+        let deviations = temperature
+            .basket
+            .iter()
+            .zip(temperature.goal.iter())
+            .filter(|(_, goal)| 0. < **goal)
+            .map(|(basket, goal)| (basket - goal).abs());
+        let temperature_deviation = Summary::of(deviations).map(|summary| summary.mean);
+
+        let water_dispensed = water_dispensed(data);
+        let puck_retention = water_dispensed
+            .zip(final_weight.or(beverage))
+            .map(|(water, beverage)| water - beverage);
+
+        Self {
+            total_time: data.elapsed.last().copied().unwrap_or_default(),
+            preinfusion_time: time_when(data, &data.pressure.pressure, |p| {
+                PREINFUSION_PRESSURE <= p
+            }),
+            first_drops_time: time_when(data, &data.totals.weight, |w| FIRST_DROPS_WEIGHT < w),
+            phases: phases(data),
+            final_weight,
+            brew_ratio,
+            temperature_deviation,
+            water_dispensed,
+            puck_retention,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::{
+        json::{ShotDataJson, SHOT1, SHOT2},
+        FlowSection, PressureSection,
+    };
+
+    fn sample(json: &str) -> ShotStats {
+        let data: ShotData = serde_json::from_str::<ShotDataJson>(json).unwrap().into();
+        ShotStats::of(&data)
+    }
+
+    fn assert_near(actual: Option<f32>, expected: f32) {
+        let actual = actual.unwrap();
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_stats_of_shot1() {
+        let stats = sample(SHOT1);
+        assert_eq!(stats.total_time, 25.068);
+        assert_eq!(stats.preinfusion_time, Some(5.039));
+        assert_eq!(stats.first_drops_time, Some(6.57));
+        assert_eq!(stats.final_weight, Some(37.3));
+        assert_near(stats.brew_ratio, 39.1 / 19.0);
+        assert_near(stats.water_dispensed, 64.902);
+        assert_near(stats.puck_retention, 64.902 - 37.3);
+        assert!(stats.temperature_deviation.unwrap() < 10.);
+
+        let names: Vec<_> = stats
+            .phases
+            .iter()
+            .map(|phase| phase.name.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["preinfusion", "dynamic bloom", "ramp", "6 bar", "decline"]
+        );
+        assert_eq!(stats.phases[0].start, 0.0);
+        assert_eq!(stats.phases[1].start, 4.544);
+        let total: f32 = stats.phases.iter().map(|phase| phase.duration).sum();
+        assert!((total - stats.total_time).abs() < 0.001);
+        for phase in stats.phases.iter() {
+            let pressure = phase.pressure.unwrap();
+            assert!(pressure.mean <= pressure.peak);
+        }
+    }
+
+    #[test]
+    fn test_stats_of_shot2() {
+        let stats = sample(SHOT2);
+        assert_eq!(stats.total_time, 59.489);
+        assert_eq!(stats.preinfusion_time, Some(5.984));
+        assert_eq!(stats.first_drops_time, Some(18.495));
+        assert_near(stats.brew_ratio, 40.4 / 20.0);
+        assert_near(stats.water_dispensed, 70.702);
+        assert_eq!(stats.phases.len(), 4);
+    }
+
+    #[test]
+    fn test_stats_without_scale_or_markers() {
+        let data = ShotData {
+            elapsed: vec![0.0, 1.0, 2.0],
+            pressure: PressureSection {
+                pressure: vec![1.0, 5.0, f32::NAN],
+                goal: vec![],
+            },
+            flow: FlowSection {
+                flow: vec![2.0, 4.0, 2.0],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let stats = ShotStats::of(&data);
+        assert_eq!(stats.preinfusion_time, Some(1.0));
+        assert_eq!(stats.first_drops_time, None);
+        assert_eq!((stats.final_weight, stats.brew_ratio), (None, None));
+        assert_eq!(stats.temperature_deviation, None);
+        assert_eq!(stats.water_dispensed, Some(6.0));
+        assert_eq!(stats.puck_retention, None);
+        assert_eq!(
+            stats.phases,
+            [PhaseStats {
+                name: None,
+                start: 0.0,
+                duration: 2.0,
+                pressure: Some(Summary {
+                    peak: 5.0,
+                    mean: 3.0
+                }),
+                flow: Some(Summary {
+                    peak: 4.0,
+                    mean: 8.0 / 3.0
+                }),
+            }]
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::{ChartData, ShotData},
+    Quantity, Range, Units,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubtitleFormat {
+    #[default]
+    WebVtt,
+    Srt,
+}
+
+impl SubtitleFormat {
+    pub const ALL: [SubtitleFormat; 2] = [SubtitleFormat::WebVtt, SubtitleFormat::Srt];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::WebVtt => "WebVTT",
+            Self::Srt => "SRT",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::WebVtt => "vtt",
+            Self::Srt => "srt",
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            Self::WebVtt => "text/vtt",
+            Self::Srt => "application/x-subrip",
+        }
+    }
+
+    pub fn write(&self, cues: &[Cue]) -> String {
+        let mut buf = String::default();
+        if *self == Self::WebVtt {
+            buf.push_str("WEBVTT\n\n");
+        }
+        for (i, cue) in cues.iter().enumerate() {
+            if *self == Self::Srt {
+                let _ = writeln!(buf, "{}", i + 1);
+            }
+            let _ = writeln!(
+                buf,
+                "{} --> {}",
+                self.timestamp(cue.start),
+                self.timestamp(cue.end)
+            );
+            let _ = writeln!(buf, "{}\n", cue.text);
+        }
+        buf
+    }
+
+    /// `hh:mm:ss.mmm`, with a comma before the milliseconds in SRT.
+    fn timestamp(&self, seconds: f32) -> String {
+        let ms = (seconds.max(0.) * 1000.).round() as u64;
+        let separator = match self {
+            Self::WebVtt => '.',
+            Self::Srt => ',',
+        };
+        format!(
+            "{:02}:{:02}:{:02}{}{:03}",
+            ms / 3_600_000,
+            ms / 60_000 % 60,
+            ms / 1000 % 60,
+            separator,
+            ms % 1000
+        )
+    }
+}
+
+/// Text shown between `start` and `end`, in seconds of the video.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cue {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+}
+
+/// One cue every `interval` seconds of the shot with the latest values and the
+/// running phase. `offset` is where the shot starts in the video, as in [`crate::Settings`].
+pub fn cues(data: &ShotData, units: &Units, interval: f32, offset: f32) -> Vec<Cue> {
+    if data.elapsed.is_empty() || interval <= 0. {
+        return vec![];
+    }
+
+    let readouts = [
+        ("Pressure", Quantity::Pressure, ChartData::pressure(data)),
+        ("Flow", Quantity::Flow, ChartData::flow(data)),
+        ("Weight", Quantity::Weight, ChartData::weight(data)),
+        ("Temp", Quantity::Temperature, ChartData::temp_basket(data)),
+    ];
+    let time_span = Range::from_series(&data.elapsed);
+    let count = ((time_span.max - time_span.min) / interval).ceil().max(1.) as usize;
+
+    (0..count)
+        .filter_map(|i| {
+            let t = time_span.min + interval * i as f32;
+            let end = (t + interval).min(time_span.max) + offset;
+            if end <= 0. {
+                return None;
+            }
+
+            let values: Vec<String> = readouts
+                .iter()
+                .map(|(label, quantity, chart)| {
+                    let value = match chart.value_at(t) {
+                        Some(v) => format!("{:.1}", units.convert(*quantity, v)),
+                        None => "-".into(),
+                    };
+                    format!("{} {} {}", label, value, units.symbol(*quantity))
+                })
+                .collect();
+            let mut text = values.join(" | ");
+            if let Some(phase) = data.phase_at(t) {
+                text.push('\n');
+                text.push_str(phase);
+            }
+
+            Some(Cue {
+                start: (t + offset).max(0.),
+                end,
+                text,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::{
+        json::{ShotDataJson, SHOT2},
+        FlowSection, PressureSection, ProfileSection, TemperatureSection, TotalsSection,
+    };
+
+    fn data() -> ShotData {
+        ShotData {
+            elapsed: vec![0.0, 1.0, 2.0, 2.5],
+            pressure: PressureSection {
+                pressure: vec![0.0, 2.0, 9.0, 8.5],
+                goal: vec![],
+            },
+            flow: FlowSection {
+                flow: vec![0.0, 1.5, 2.0, 2.0],
+                ..Default::default()
+            },
+            temperature: TemperatureSection {
+                basket: vec![90.0, 91.0, 92.0, 92.0],
+                ..Default::default()
+            },
+            totals: TotalsSection {
+                weight: vec![0.0, 0.0, 1.2, 3.0],
+                ..Default::default()
+            },
+            state_change: vec![0.0, 1e7, -1e7, -1e7],
+            profile: ProfileSection {
+                title: "".into(),
+                steps: vec!["fill".into(), "pour".into()],
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cues() {
+        let actual = cues(&data(), &Units::METRIC, 1.0, 0.5);
+        assert_eq!(actual.len(), 3);
+        assert_eq!(actual[1].start, 1.5);
+        assert_eq!(actual[1].end, 2.5);
+        assert_eq!(
+            actual[1].text,
+            "Pressure 2.0 bar | Flow 1.5 ml/s | Weight 0.0 g | Temp 91.0 °C\nfill"
+        );
+        assert_eq!(actual[2].end, 3.0);
+        assert!(actual[2].text.ends_with("\npour"));
+    }
+
+    #[test]
+    fn test_cues_before_video_starts() {
+        let actual = cues(&data(), &Units::METRIC, 1.0, -1.5);
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual[0].start, 0.0);
+        assert_eq!(actual[0].end, 0.5);
+    }
+
+    #[test]
+    fn test_write_vtt() {
+        let cues = [Cue {
+            start: 61.25,
+            end: 3725.5,
+            text: "Pressure 9.0 bar\npour".into(),
+        }];
+        assert_eq!(
+            SubtitleFormat::WebVtt.write(&cues),
+            "WEBVTT\n\n00:01:01.250 --> 01:02:05.500\nPressure 9.0 bar\npour\n\n"
+        );
+    }
+
+    #[test]
+    fn test_write_srt() {
+        let cue = Cue {
+            start: 0.0,
+            end: 1.0,
+            text: "a".into(),
+        };
+        assert_eq!(
+            SubtitleFormat::Srt.write(&[cue.clone(), cue]),
+            "1\n00:00:00,000 --> 00:00:01,000\na\n\n2\n00:00:00,000 --> 00:00:01,000\na\n\n"
+        );
+    }
+
+    #[test]
+    fn test_cues_of_shot2() {
+        let data: ShotData = serde_json::from_str::<ShotDataJson>(SHOT2).unwrap().into();
+        let units = Units::from_shot(&data);
+        let cues = cues(&data, &units, 1.0, 0.0);
+        assert!(!cues.is_empty());
+        assert!(cues[0].text.ends_with("\npreinfusion"));
+        assert!(cues.last().unwrap().text.ends_with("\n6 bar"));
+    }
+}
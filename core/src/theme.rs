@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::SeriesKind;
+
+/// Colours used to draw the overlay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub pressure: &'static str,
+    pub flow: &'static str,
+    pub flow_by_weight: &'static str,
+    pub temp_basket: &'static str,
+    pub temp_mix: &'static str,
+    pub axis: &'static str,
+    pub text: &'static str,
+    pub panel: &'static str,
+}
+
+impl Theme {
+    pub fn color(&self, kind: SeriesKind) -> &'static str {
+        match kind {
+            SeriesKind::Pressure => self.pressure,
+            SeriesKind::Flow => self.flow,
+            SeriesKind::FlowByWeight => self.flow_by_weight,
+            SeriesKind::TempBasket => self.temp_basket,
+            SeriesKind::TempMix => self.temp_mix,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ThemePreset {
+    #[default]
+    Classic,
+    Visualizer,
+    HighContrast,
+    ColorBlind,
+}
+
+impl ThemePreset {
+    pub const ALL: [ThemePreset; 4] = [
+        ThemePreset::Classic,
+        ThemePreset::Visualizer,
+        ThemePreset::HighContrast,
+        ThemePreset::ColorBlind,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Classic => "DE1 classic",
+            Self::Visualizer => "Visualizer",
+            Self::HighContrast => "High contrast",
+            Self::ColorBlind => "Colour-blind safe",
+        }
+    }
+
+    /// Short name used on the command line and in links.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Self::Classic => "classic",
+            Self::Visualizer => "visualizer",
+            Self::HighContrast => "high-contrast",
+            Self::ColorBlind => "color-blind",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|preset| preset.slug() == slug)
+    }
+
+    pub fn theme(&self) -> Theme {
+        match self {
+            Self::Classic => Theme {
+                pressure: "darkgreen",
+                flow: "#1f77ea",
+                flow_by_weight: "#8f6400",
+                temp_basket: "darkred",
+                temp_mix: "red",
+                axis: "#7f7f7f",
+                text: "#404040",
+                panel: "rgba(255, 255, 255, 0.6)",
+            },
+            Self::Visualizer => Theme {
+                pressure: "#05c793",
+                flow: "#1fb7ea",
+                flow_by_weight: "#a2693d",
+                temp_basket: "#e73249",
+                temp_mix: "#ff9900",
+                axis: "#a0a0a0",
+                text: "#e0e0e0",
+                panel: "rgba(24, 24, 32, 0.6)",
+            },
+            Self::HighContrast => Theme {
+                pressure: "#00ff00",
+                flow: "#00ffff",
+                flow_by_weight: "#ffff00",
+                temp_basket: "#ff0000",
+                temp_mix: "#ff00ff",
+                axis: "#ffffff",
+                text: "#ffffff",
+                panel: "rgba(0, 0, 0, 0.75)",
+            },
+            // Okabe-Ito palette
+            Self::ColorBlind => Theme {
+                pressure: "#009e73",
+                flow: "#0072b2",
+                flow_by_weight: "#e69f00",
+                temp_basket: "#d55e00",
+                temp_mix: "#cc79a7",
+                axis: "#999999",
+                text: "#f0f0f0",
+                panel: "rgba(0, 0, 0, 0.5)",
+            },
+        }
+    }
+}
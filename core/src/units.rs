@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::ShotData;
+
+/// Physical quantity of a value recorded in a shot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantity {
+    Pressure,
+    Flow,
+    FlowByWeight,
+    Weight,
+    Temperature,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PressureUnit {
+    #[default]
+    Bar,
+    Psi,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum VolumeUnit {
+    #[default]
+    Millilitre,
+    FluidOunce,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum WeightUnit {
+    #[default]
+    Gram,
+    Ounce,
+}
+
+const PSI_PER_BAR: f32 = 14.503_774;
+const ML_PER_FL_OZ: f32 = 29.573_53;
+const G_PER_OZ: f32 = 28.349_524;
+
+impl TemperatureUnit {
+    pub const ALL: [TemperatureUnit; 2] = [TemperatureUnit::Celsius, TemperatureUnit::Fahrenheit];
+
+    pub fn convert(&self, celsius: f32) -> f32 {
+        match self {
+            Self::Celsius => celsius,
+            Self::Fahrenheit => celsius * 9. / 5. + 32.,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Celsius => "°C",
+            Self::Fahrenheit => "°F",
+        }
+    }
+}
+
+impl PressureUnit {
+    pub const ALL: [PressureUnit; 2] = [PressureUnit::Bar, PressureUnit::Psi];
+
+    pub fn convert(&self, bar: f32) -> f32 {
+        match self {
+            Self::Bar => bar,
+            Self::Psi => bar * PSI_PER_BAR,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Bar => "bar",
+            Self::Psi => "psi",
+        }
+    }
+}
+
+impl VolumeUnit {
+    pub const ALL: [VolumeUnit; 2] = [VolumeUnit::Millilitre, VolumeUnit::FluidOunce];
+
+    pub fn convert(&self, ml: f32) -> f32 {
+        match self {
+            Self::Millilitre => ml,
+            Self::FluidOunce => ml / ML_PER_FL_OZ,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Millilitre => "ml",
+            Self::FluidOunce => "fl oz",
+        }
+    }
+}
+
+impl WeightUnit {
+    pub const ALL: [WeightUnit; 2] = [WeightUnit::Gram, WeightUnit::Ounce];
+
+    pub fn convert(&self, g: f32) -> f32 {
+        match self {
+            Self::Gram => g,
+            Self::Ounce => g / G_PER_OZ,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Gram => "g",
+            Self::Ounce => "oz",
+        }
+    }
+}
+
+/// Unit system used to display values. Shot files always record °C, bar, ml and g.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Units {
+    pub temperature: TemperatureUnit,
+    pub pressure: PressureUnit,
+    pub volume: VolumeUnit,
+    pub weight: WeightUnit,
+}
+
+impl Units {
+    pub const METRIC: Units = Units {
+        temperature: TemperatureUnit::Celsius,
+        pressure: PressureUnit::Bar,
+        volume: VolumeUnit::Millilitre,
+        weight: WeightUnit::Gram,
+    };
+
+    pub const IMPERIAL: Units = Units {
+        temperature: TemperatureUnit::Fahrenheit,
+        pressure: PressureUnit::Psi,
+        volume: VolumeUnit::FluidOunce,
+        weight: WeightUnit::Ounce,
+    };
+
+    /// Units the DE1 app was set to display when the shot was pulled.
+    pub fn from_shot(data: &ShotData) -> Self {
+        Self {
+            temperature: if data.app.fahrenheit {
+                TemperatureUnit::Fahrenheit
+            } else {
+                TemperatureUnit::Celsius
+            },
+            volume: if data.app.fluid_ounces {
+                VolumeUnit::FluidOunce
+            } else {
+                VolumeUnit::Millilitre
+            },
+            ..Self::METRIC
+        }
+    }
+
+    pub fn convert(&self, quantity: Quantity, value: f32) -> f32 {
+        match quantity {
+            Quantity::Pressure => self.pressure.convert(value),
+            Quantity::Flow => self.volume.convert(value),
+            Quantity::FlowByWeight | Quantity::Weight => self.weight.convert(value),
+            Quantity::Temperature => self.temperature.convert(value),
+        }
+    }
+
+    pub fn symbol(&self, quantity: Quantity) -> String {
+        match quantity {
+            Quantity::Pressure => self.pressure.symbol().into(),
+            Quantity::Flow => format!("{}/s", self.volume.symbol()),
+            Quantity::FlowByWeight => format!("{}/s", self.weight.symbol()),
+            Quantity::Weight => self.weight.symbol().into(),
+            Quantity::Temperature => self.temperature.symbol().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::AppSection;
+
+    #[test]
+    fn test_units_convert() {
+        let units = Units::IMPERIAL;
+        assert_eq!(units.convert(Quantity::Temperature, 100.), 212.);
+        assert_eq!(units.convert(Quantity::Pressure, 1.), PSI_PER_BAR);
+        assert!((units.convert(Quantity::Flow, 59.147_06) - 2.).abs() < 1e-5);
+        assert!((units.convert(Quantity::FlowByWeight, 28.349_524) - 1.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_units_from_shot() {
+        let data = ShotData {
+            app: AppSection {
+                fahrenheit: true,
+                fluid_ounces: false,
+            },
+            ..Default::default()
+        };
+        let expected = Units {
+            temperature: TemperatureUnit::Fahrenheit,
+            ..Units::METRIC
+        };
+        assert_eq!(Units::from_shot(&data), expected);
+    }
+}
@@ -0,0 +1,280 @@
+use std::fmt;
+
+use crate::models::ShotData;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Worth knowing, e.g. a shot pulled without a scale.
+    Info,
+    /// Drawn, but some samples are likely wrong.
+    Warning,
+    /// Can't be drawn as recorded.
+    Error,
+}
+
+impl Severity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Info => "Info",
+            Self::Warning => "Warning",
+            Self::Error => "Error",
+        }
+    }
+}
+
+/// One problem found in a shot.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// Seconds into the shot of the first affected sample, if there is one.
+    pub t: Option<f32>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity.label(), self.message)?;
+        if let Some(t) = self.t {
+            write!(f, " (at {:.1} s)", t)?;
+        }
+        Ok(())
+    }
+}
+
+/// Weight lost from one sample to the next that is put down to the scale.
+const DROPOUT: f32 = 1.0;
+
+/// Every series recorded against `elapsed`.
+fn all_series(data: &ShotData) -> [(&'static str, &[f32]); 14] {
+    [
+        ("Pressure", &data.pressure.pressure),
+        ("Pressure goal", &data.pressure.goal),
+        ("Flow", &data.flow.flow),
+        ("Flow by weight", &data.flow.by_weight),
+        ("Raw flow by weight", &data.flow.by_weight_raw),
+        ("Flow goal", &data.flow.goal),
+        ("Basket temperature", &data.temperature.basket),
+        ("Mix temperature", &data.temperature.mix),
+        ("Temperature goal", &data.temperature.goal),
+        ("Weight", &data.totals.weight),
+        ("Water dispensed", &data.totals.water_dispensed),
+        ("Resistance", &data.resistance.resistance),
+        ("Resistance by weight", &data.resistance.by_weight),
+        ("State change", &data.state_change),
+    ]
+}
+
+/// Measured series with the values a DE1 can plausibly record, in metric units.
+fn plausible_ranges(data: &ShotData) -> [(&'static str, &[f32], f32, f32); 5] {
+    [
+        ("Pressure", &data.pressure.pressure, -0.5, 16.),
+        ("Flow", &data.flow.flow, -0.5, 15.),
+        ("Basket temperature", &data.temperature.basket, 0., 110.),
+        ("Mix temperature", &data.temperature.mix, 0., 110.),
+        ("Weight", &data.totals.weight, -5., 1000.),
+    ]
+}
+
+/// Counts the samples matching `f` and the time of the first of them.
+fn find(data: &ShotData, len: usize, f: impl Fn(usize) -> bool) -> (usize, Option<f32>) {
+    let found: Vec<usize> = (0..len).filter(|i| f(*i)).collect();
+    let t = found.first().and_then(|i| data.elapsed.get(*i)).copied();
+    (found.len(), t)
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+/// Looks for what parsing lets through but makes a shot wrong: series that
+/// don't line up with the time axis, time going backwards, repeated samples,
+/// scale dropouts and values no machine records. Worst problems come first.
+pub fn validate(data: &ShotData) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut push = |severity, message: String, t| {
+        diagnostics.push(Diagnostic {
+            severity,
+            message,
+            t,
+        })
+    };
+
+    let elapsed = &data.elapsed;
+    if elapsed.is_empty() {
+        push(Severity::Error, "The shot has no samples".into(), None);
+        return diagnostics;
+    }
+
+    for (label, values) in all_series(data) {
+        if !values.is_empty() && values.len() != elapsed.len() {
+            push(
+                Severity::Error,
+                format!(
+                    "{} has {} for {} points in time",
+                    label,
+                    plural(values.len(), "sample"),
+                    elapsed.len()
+                ),
+                None,
+            );
+        }
+    }
+
+    let (count, t) = find(data, elapsed.len(), |i| !elapsed[i].is_finite());
+    if 0 < count {
+        push(
+            Severity::Error,
+            format!("Time is missing for {}", plural(count, "sample")),
+            t,
+        );
+    }
+    let (count, t) = find(data, elapsed.len(), |i| {
+        0 < i && elapsed[i] < elapsed[i - 1]
+    });
+    if 0 < count {
+        push(
+            Severity::Error,
+            format!("Time goes backwards at {}", plural(count, "point")),
+            t,
+        );
+    }
+    let (count, t) = find(data, elapsed.len(), |i| {
+        0 < i && elapsed[i] == elapsed[i - 1]
+    });
+    if 0 < count {
+        push(
+            Severity::Warning,
+            format!("Time repeats at {}", plural(count, "point")),
+            t,
+        );
+    }
+
+    for (label, values) in all_series(data) {
+        let (count, t) = find(data, values.len(), |i| !values[i].is_finite());
+        if 0 < count {
+            push(
+                Severity::Warning,
+                format!("{} is missing {}", label, plural(count, "sample")),
+                t,
+            );
+        }
+    }
+
+    for (label, values, min, max) in plausible_ranges(data) {
+        let (count, t) = find(data, values.len(), |i| values[i] < min || max < values[i]);
+        if 0 < count {
+            push(
+                Severity::Warning,
+                format!(
+                    "{} is outside {} to {} for {}",
+                    label,
+                    min,
+                    max,
+                    plural(count, "sample")
+                ),
+                t,
+            );
+        }
+    }
+
+    let weight = &data.totals.weight;
+    let (count, t) = find(data, weight.len(), |i| {
+        0 < i && weight[i - 1] - weight[i] > DROPOUT
+    });
+    if 0 < count {
+        push(
+            Severity::Warning,
+            format!(
+                "Weight drops suddenly at {}, as in a scale dropout",
+                plural(count, "point")
+            ),
+            t,
+        );
+    }
+    if weight.iter().all(|w| *w == 0.) {
+        push(
+            Severity::Info,
+            "No weight was recorded; the shot was likely pulled without a scale".into(),
+            None,
+        );
+    }
+
+    diagnostics.sort_by_key(|diagnostic| std::cmp::Reverse(diagnostic.severity));
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::models::{json::ShotDataJson, json::SHOT2, PressureSection, TotalsSection};
+
+    fn shot() -> ShotData {
+        ShotData {
+            elapsed: vec![0.0, 1.0, 2.0, 3.0],
+            pressure: PressureSection {
+                pressure: vec![0.0, 2.0, 6.0, 9.0],
+                goal: vec![],
+            },
+            totals: TotalsSection {
+                weight: vec![0.0, 0.0, 1.5, 4.0],
+                water_dispensed: vec![],
+            },
+            ..Default::default()
+        }
+    }
+
+    fn messages(data: &ShotData) -> Vec<String> {
+        validate(data).iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_validate_clean_shots() {
+        assert!(validate(&shot()).is_empty());
+        let data: ShotData = serde_json::from_str::<ShotDataJson>(SHOT2).unwrap().into();
+        assert_eq!(validate(&data), []);
+    }
+
+    #[test]
+    fn test_validate_time() {
+        let mut data = shot();
+        data.elapsed = vec![0.0, 1.0, 1.0, 0.5];
+        assert_eq!(
+            messages(&data),
+            [
+                "Error: Time goes backwards at 1 point (at 0.5 s)",
+                "Warning: Time repeats at 1 point (at 1.0 s)",
+            ]
+        );
+
+        data.elapsed = vec![];
+        assert_eq!(messages(&data), ["Error: The shot has no samples"]);
+    }
+
+    #[test]
+    fn test_validate_series() {
+        let mut data = shot();
+        data.pressure.pressure = vec![0.0, f32::NAN, 25.0];
+        data.totals.weight = vec![0.0, 12.0, 0.0, 13.0];
+        assert_eq!(
+            messages(&data),
+            [
+                "Error: Pressure has 3 samples for 4 points in time",
+                "Warning: Pressure is missing 1 sample (at 1.0 s)",
+                "Warning: Pressure is outside -0.5 to 16 for 1 sample (at 2.0 s)",
+                "Warning: Weight drops suddenly at 1 point, as in a scale dropout (at 2.0 s)",
+            ]
+        );
+
+        data.pressure.pressure = vec![];
+        data.totals.weight = vec![0.0; 4];
+        assert_eq!(
+            messages(&data),
+            ["Info: No weight was recorded; the shot was likely pulled without a scale"]
+        );
+    }
+}
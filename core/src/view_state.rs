@@ -0,0 +1,148 @@
+use crate::{models::SeriesKind, Settings, ThemePreset};
+
+/// One letter per series in links, e.g. `s=pfb`.
+fn series_code(kind: SeriesKind) -> char {
+    match kind {
+        SeriesKind::Pressure => 'p',
+        SeriesKind::Flow => 'f',
+        SeriesKind::FlowByWeight => 'w',
+        SeriesKind::TempBasket => 'b',
+        SeriesKind::TempMix => 'm',
+    }
+}
+
+/// Prints `value` with at most two decimals and no trailing zeros.
+fn number(value: f32) -> String {
+    format!("{}", (value * 100.).round() / 100.)
+}
+
+/// What a shared link to the player restores. Every field is optional so that
+/// hand-written or truncated links still open with the rest left as is.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ViewState {
+    /// Seconds into the shot.
+    pub t: Option<f32>,
+    pub offset: Option<f32>,
+    pub series: Option<Vec<SeriesKind>>,
+    pub theme: Option<ThemePreset>,
+}
+
+impl ViewState {
+    pub fn new(settings: &Settings, t: f32) -> Self {
+        Self {
+            t: Some(t),
+            offset: Some(settings.offset),
+            series: Some(settings.series.clone()),
+            theme: Some(settings.theme),
+        }
+    }
+
+    pub fn apply(&self, settings: &mut Settings) {
+        if let Some(offset) = self.offset {
+            settings.offset = offset;
+        }
+        if let Some(series) = &self.series {
+            settings.series = series.clone();
+        }
+        if let Some(theme) = self.theme {
+            settings.theme = theme;
+        }
+    }
+
+    /// Query string parameters, with short keys to keep links short.
+    pub fn to_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = vec![];
+        if let Some(t) = self.t {
+            pairs.push(("t", number(t)));
+        }
+        if let Some(offset) = self.offset {
+            pairs.push(("o", number(offset)));
+        }
+        if let Some(series) = &self.series {
+            pairs.push(("s", series.iter().map(|kind| series_code(*kind)).collect()));
+        }
+        if let Some(theme) = self.theme {
+            pairs.push(("th", theme.slug().into()));
+        }
+        pairs
+    }
+
+    /// Reads parameters written by [`ViewState::to_pairs`], skipping unknown or invalid ones.
+    pub fn from_pairs<K: AsRef<str>, V: AsRef<str>>(
+        pairs: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        let mut state = Self::default();
+        for (key, value) in pairs {
+            let value = value.as_ref();
+            match key.as_ref() {
+                "t" => {
+                    state.t = value
+                        .parse()
+                        .ok()
+                        .filter(|t: &f32| t.is_finite() && 0. <= *t)
+                }
+                "o" => state.offset = value.parse().ok().filter(|o: &f32| o.is_finite()),
+                "s" => {
+                    state.series = Some(
+                        SeriesKind::ALL
+                            .into_iter()
+                            .filter(|kind| value.contains(series_code(*kind)))
+                            .collect(),
+                    )
+                }
+                "th" => state.theme = ThemePreset::from_slug(value),
+                _ => {}
+            }
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_state_round_trip() {
+        let settings = Settings {
+            offset: -1.25,
+            series: vec![SeriesKind::Pressure, SeriesKind::TempBasket],
+            theme: ThemePreset::HighContrast,
+            ..Default::default()
+        };
+        let state = ViewState::new(&settings, 12.345);
+        let pairs = state.to_pairs();
+        assert_eq!(
+            pairs,
+            vec![
+                ("t", "12.35".to_string()),
+                ("o", "-1.25".to_string()),
+                ("s", "pb".to_string()),
+                ("th", "high-contrast".to_string()),
+            ]
+        );
+
+        let actual = ViewState::from_pairs(pairs);
+        assert_eq!(actual.t, Some(12.35));
+        let mut restored = Settings::default();
+        actual.apply(&mut restored);
+        assert_eq!(restored, settings);
+    }
+
+    #[test]
+    fn test_view_state_skips_invalid_pairs() {
+        let state = ViewState::from_pairs([("t", "abc"), ("th", "neon"), ("x", "1"), ("o", "2")]);
+        assert_eq!(
+            state,
+            ViewState {
+                offset: Some(2.),
+                ..Default::default()
+            }
+        );
+
+        let mut settings = Settings::default();
+        state.apply(&mut settings);
+        assert_eq!(settings.theme, ThemePreset::default());
+        assert_eq!(settings.series, SeriesKind::ALL.to_vec());
+    }
+}
@@ -0,0 +1,232 @@
+//! Renders the overlay of a shot into a sequence of transparent PNG frames,
+//! to be laid on a track above the footage in a video editor, or into an ASS
+//! script to burn in with ffmpeg.
+
+use std::fs;
+use std::path::PathBuf;
+
+use resvg::{tiny_skia, usvg};
+
+use decent_overlay_core::{
+    frame_count,
+    models::{json::ShotDataJson, ChartData, ShotData},
+    to_ass, validate, Overlay, Placement, Range, Settings, ThemePreset, Units,
+};
+
+const USAGE: &str = "\
+Usage: dvo-render <shot.json> [options]
+
+Options:
+  -o, --output <dir>       Directory to write frames into [default: frames]
+  -w, --width <px>         Frame width [default: 1080]
+      --height <px>        Frame height [default: 1920]
+      --fps <n>            Frames per second [default: 30]
+      --scale <n>          Size of text and strokes relative to a 720p frame [default: height / 720]
+      --theme <name>       classic | visualizer | high-contrast | color-blind [default: classic]
+      --placement <name>   top-left | top-right | bottom-left | bottom-right | full-width [default: full-width]
+      --units <name>       shot | metric | imperial [default: shot]
+      --opacity <0-1>      Overlay opacity [default: 1]
+      --panel              Draw a background panel behind the graph
+      --ass                Write an ASS script to <dir>/overlay.ass instead of PNG frames,
+                           to burn in with `ffmpeg -vf ass=overlay.ass`
+  -h, --help               Print this help";
+
+struct Args {
+    shot: PathBuf,
+    output: PathBuf,
+    size: (u32, u32),
+    fps: f32,
+    scale: Option<f32>,
+    units: Option<Units>,
+    settings: Settings,
+    ass: bool,
+}
+
+fn value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("missing value for {}", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value for {}: {}", flag, value))
+}
+
+fn parse(args: Vec<String>) -> Result<Args, String> {
+    let mut shot = None;
+    let mut output = PathBuf::from("frames");
+    let mut size = (1080, 1920);
+    let mut fps = 30.;
+    let mut scale = None;
+    let mut units = None;
+    let mut settings = Settings::default();
+    let mut ass = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => output = value(&arg, args.next())?,
+            "-w" | "--width" => size.0 = value(&arg, args.next())?,
+            "--height" => size.1 = value(&arg, args.next())?,
+            "--fps" => fps = value(&arg, args.next())?,
+            "--scale" => scale = Some(value(&arg, args.next())?),
+            "--opacity" => settings.opacity = value(&arg, args.next())?,
+            "--panel" => settings.panel = true,
+            "--ass" => ass = true,
+            "--theme" => {
+                let theme = value::<String>(&arg, args.next())?;
+                settings.theme = ThemePreset::from_slug(&theme)
+                    .ok_or_else(|| format!("unknown theme: {}", theme))?;
+            }
+            "--placement" => {
+                settings.placement = match value::<String>(&arg, args.next())?.as_str() {
+                    "top-left" => Placement::TopLeft,
+                    "top-right" => Placement::TopRight,
+                    "bottom-left" => Placement::BottomLeft,
+                    "bottom-right" => Placement::BottomRight,
+                    "full-width" => Placement::FullWidth,
+                    other => return Err(format!("unknown placement: {}", other)),
+                }
+            }
+            "--units" => {
+                units = match value::<String>(&arg, args.next())?.as_str() {
+                    "shot" => None,
+                    "metric" => Some(Units::METRIC),
+                    "imperial" => Some(Units::IMPERIAL),
+                    other => return Err(format!("unknown units: {}", other)),
+                }
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option: {}", arg)),
+            _ if shot.is_none() => shot = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument: {}", arg)),
+        }
+    }
+
+    if size.0 == 0 || size.1 == 0 || fps <= 0. {
+        return Err("size and fps must be positive".into());
+    }
+
+    Ok(Args {
+        shot: shot.ok_or("missing shot file")?,
+        output,
+        size,
+        fps,
+        scale,
+        units,
+        settings,
+        ass,
+    })
+}
+
+/// Loads system fonts and makes sure `sans-serif` resolves to one of them,
+/// as the default family (Arial) is often missing on Linux.
+fn load_fonts(fontdb: &mut usvg::fontdb::Database) {
+    fontdb.load_system_fonts();
+    let query = usvg::fontdb::Query {
+        families: &[usvg::fontdb::Family::SansSerif],
+        ..Default::default()
+    };
+    if fontdb.query(&query).is_none() {
+        let families: Vec<String> = fontdb
+            .faces()
+            .filter_map(|face| face.families.first().map(|(name, _)| name.clone()))
+            .collect();
+        let fallback = families
+            .iter()
+            .find(|name| name.contains("Sans") && !name.contains("Mono"))
+            .or(families.first())
+            .cloned();
+        match fallback {
+            Some(family) => fontdb.set_sans_serif_family(family),
+            None => eprintln!("warning: no fonts found, text will be missing"),
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let args = parse(args)?;
+
+    let json =
+        fs::read_to_string(&args.shot).map_err(|e| format!("{}: {}", args.shot.display(), e))?;
+    let data: ShotData = serde_json::from_str::<ShotDataJson>(&json)
+        .map_err(|e| format!("{}: {}", args.shot.display(), e))?
+        .into();
+    if data.elapsed.is_empty() {
+        return Err(format!("{}: no samples", args.shot.display()));
+    }
+    for diagnostic in validate(&data) {
+        eprintln!("{}: {}", args.shot.display(), diagnostic);
+    }
+
+    let units = args.units.unwrap_or_else(|| Units::from_shot(&data));
+    let charts = ChartData::all_in(&data, &units);
+    let time_span = Range::from_series(&data.elapsed);
+
+    // Lay the overlay out on a smaller logical frame and scale it up, so that
+    // text and strokes keep their on-screen proportions at any resolution.
+    let (width, height) = args.size;
+    let scale = args.scale.unwrap_or(height as f32 / 720.);
+    let logical = (width as f32 / scale, height as f32 / scale);
+
+    fs::create_dir_all(&args.output).map_err(|e| format!("{}: {}", args.output.display(), e))?;
+
+    if args.ass {
+        // libass scales the script from its logical size to the video's.
+        let ass = to_ass(
+            &charts,
+            &units,
+            &time_span,
+            &args.settings,
+            logical,
+            1. / args.fps,
+        );
+        let path = args.output.join("overlay.ass");
+        fs::write(&path, ass).map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!("Wrote {}", path.display());
+        return Ok(());
+    }
+
+    let mut options = usvg::Options::default();
+    load_fonts(options.fontdb_mut());
+
+    let frames = frame_count(&time_span, args.fps);
+    for i in 0..frames {
+        let t = time_span.min + i as f32 / args.fps;
+        let overlay = Overlay::build(&charts, &units, &time_span, t, logical, &args.settings);
+        let tree = usvg::Tree::from_str(&overlay.to_svg(None), &options)
+            .map_err(|e| format!("frame {}: {}", i, e))?;
+
+        let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or("failed to allocate frame")?;
+        resvg::render(
+            &tree,
+            tiny_skia::Transform::from_scale(scale, scale),
+            &mut pixmap.as_mut(),
+        );
+
+        let path = args.output.join(format!("frame_{:05}.png", i));
+        pixmap
+            .save_png(&path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+
+    println!(
+        "Wrote {} frames ({}x{} @ {} fps) to {}",
+        frames,
+        width,
+        height,
+        args.fps,
+        args.output.display()
+    );
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run(std::env::args().skip(1).collect()) {
+        eprintln!("error: {}", e);
+        eprintln!();
+        eprintln!("{}", USAGE);
+        std::process::exit(1);
+    }
+}
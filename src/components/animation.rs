@@ -0,0 +1,334 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use decent_overlay_core::{
+    frame_count,
+    models::{ChartData, SeriesKind},
+    AnimationEncoder, AnimationFormat, Overlay, Range, Settings, Units,
+};
+use gloo::events::EventListener;
+use gloo::timers::{callback::Interval, future::TimeoutFuture};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{HtmlInputElement, HtmlMediaElement, HtmlSelectElement, HtmlVideoElement};
+use yew::{platform::spawn_local, Event, NodeRef, TargetCast};
+
+use crate::components::canvas::{context_2d, create_canvas, draw_frame};
+use crate::prelude::*;
+use crate::web::download_bytes;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub charts: Rc<Vec<(SeriesKind, Rc<ChartData>)>>,
+    pub units: Units,
+    pub time_span: Range,
+    pub settings: Settings,
+    /// Size of the video frame the overlay is laid out on.
+    pub size: (f32, f32),
+    pub video: NodeRef,
+}
+
+pub enum Msg {
+    SetFormat(AnimationFormat),
+    SetFps(f32),
+    SetWidth(u16),
+    SetWithVideo(bool),
+    Start,
+    Cancel,
+    Progress(u32),
+    Finished(Result<Option<Vec<u8>>, JsValue>),
+}
+
+struct Job {
+    total: u32,
+    done: u32,
+    cancelled: Rc<Cell<bool>>,
+}
+
+/// Everything needed to render the frames, detached from the component.
+struct Task {
+    charts: Rc<Vec<(SeriesKind, Rc<ChartData>)>>,
+    units: Units,
+    time_span: Range,
+    settings: Settings,
+    size: (f32, f32),
+    video: Option<HtmlVideoElement>,
+    format: AnimationFormat,
+    fps: f32,
+    width: u16,
+}
+
+/// How often a pending seek checks whether the export was cancelled, in ms.
+const SEEK_POLL: u32 = 100;
+/// Longest a seek may take before the export gives up, in ms.
+const SEEK_TIMEOUT: u32 = 10_000;
+
+/// Seeks `video` to `t` seconds and waits until the picture at that time is
+/// available. Returns `false` if `cancelled` is set in the meantime, and fails
+/// if the video can't be read or takes too long.
+async fn seek(
+    video: &HtmlVideoElement,
+    t: f64,
+    cancelled: &Rc<Cell<bool>>,
+) -> Result<bool, JsValue> {
+    if video.network_state() == HtmlMediaElement::NETWORK_NO_SOURCE {
+        return Err("The video has no source".into());
+    }
+
+    let mut listeners = vec![];
+    let mut poll = None;
+    let settled = js_sys::Promise::new(&mut |resolve, reject| {
+        let seeked = resolve.clone();
+        listeners.push(EventListener::once(video, "seeked", move |_| {
+            let _ = seeked.call1(&JsValue::NULL, &JsValue::TRUE);
+        }));
+        let failed = reject.clone();
+        listeners.push(EventListener::once(video, "error", move |_| {
+            let _ = failed.call1(&JsValue::NULL, &"The video failed to load".into());
+        }));
+        let cancelled = cancelled.clone();
+        let mut waited = 0;
+        poll = Some(Interval::new(SEEK_POLL, move || {
+            waited += SEEK_POLL;
+            if cancelled.get() {
+                let _ = resolve.call1(&JsValue::NULL, &JsValue::FALSE);
+            } else if SEEK_TIMEOUT <= waited {
+                let _ = reject.call1(&JsValue::NULL, &"Seeking the video timed out".into());
+            }
+        }));
+    });
+    video.set_current_time(t);
+    let seeked = JsFuture::from(settled).await;
+    drop(listeners);
+    drop(poll);
+    Ok(seeked?.is_truthy())
+}
+
+impl Task {
+    /// Renders and encodes every frame, or returns `None` once `cancelled` is set.
+    async fn run(
+        &self,
+        cancelled: &Rc<Cell<bool>>,
+        progress: impl Fn(u32),
+    ) -> Result<Option<Vec<u8>>, JsValue> {
+        let scale = self.width as f64 / self.size.0 as f64;
+        let height = (self.size.1 as f64 * scale).round() as u16;
+        let canvas = create_canvas(self.width as u32, height as u32)?;
+        let ctx = context_2d(&canvas).ok_or("Missing 2d context")?;
+
+        let frames = frame_count(&self.time_span, self.fps);
+        let mut encoder =
+            AnimationEncoder::new(self.format, (self.width, height), frames, self.fps)?;
+        for i in 0..frames {
+            if cancelled.get() {
+                return Ok(None);
+            }
+
+            let t = self.time_span.min + i as f32 / self.fps;
+            match &self.video {
+                Some(video) => {
+                    if !seek(video, (t + self.settings.offset) as f64, cancelled).await? {
+                        return Ok(None);
+                    }
+                }
+                // Let the page repaint the progress bar between frames.
+                None => TimeoutFuture::new(0).await,
+            }
+
+            let overlay = Overlay::build(
+                &self.charts,
+                &self.units,
+                &self.time_span,
+                t,
+                self.size,
+                &self.settings,
+            );
+            ctx.clear_rect(0., 0., self.width as f64, height as f64);
+            draw_frame(&ctx, &overlay, self.video.as_ref(), scale)?;
+            let mut rgba = ctx
+                .get_image_data(0., 0., self.width as f64, height as f64)?
+                .data();
+            encoder.push(&mut rgba)?;
+            progress(i + 1);
+        }
+
+        Ok(Some(encoder.finish()?))
+    }
+}
+
+/// Encodes the whole overlay animation into a GIF or APNG in the browser.
+pub struct ExportAnimation {
+    format: AnimationFormat,
+    fps: f32,
+    width: u16,
+    with_video: bool,
+    job: Option<Job>,
+}
+
+impl ExportAnimation {
+    fn start(&mut self, ctx: &Context<Self>) {
+        let props = ctx.props();
+        let video = self
+            .with_video
+            .then(|| props.video.cast::<HtmlVideoElement>())
+            .flatten();
+        if let Some(video) = &video {
+            let _ = video.pause();
+        }
+
+        let task = Task {
+            charts: props.charts.clone(),
+            units: props.units,
+            time_span: props.time_span.clone(),
+            settings: props.settings.clone(),
+            size: props.size,
+            video,
+            format: self.format,
+            fps: self.fps,
+            width: self.width,
+        };
+        let cancelled = Rc::new(Cell::new(false));
+        self.job = Some(Job {
+            total: frame_count(&props.time_span, self.fps),
+            done: 0,
+            cancelled: cancelled.clone(),
+        });
+
+        let link = ctx.link().clone();
+        spawn_local(async move {
+            let restore = task.video.as_ref().map(|video| video.current_time());
+            let result = task
+                .run(&cancelled, |done| link.send_message(Msg::Progress(done)))
+                .await;
+            if let (Some(video), Some(t)) = (&task.video, restore) {
+                video.set_current_time(t);
+            }
+            link.send_message(Msg::Finished(result));
+        });
+    }
+
+    fn render_progress(&self, ctx: &Context<Self>, job: &Job) -> Html {
+        html! {
+            <>
+                <progress max={ format!("{}", job.total) } value={ format!("{}", job.done) } />
+                <span>{ format!("{} / {}", job.done, job.total) }</span>
+                <button onclick={ctx.link().callback(|_| Msg::Cancel)}>{ "Cancel" }</button>
+            </>
+        }
+    }
+}
+
+impl Component for ExportAnimation {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self {
+            format: AnimationFormat::default(),
+            fps: 10.,
+            width: 540,
+            with_video: false,
+            job: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetFormat(format) => self.format = format,
+            Msg::SetFps(fps) => self.fps = fps,
+            Msg::SetWidth(width) => self.width = width,
+            Msg::SetWithVideo(with_video) => self.with_video = with_video,
+            Msg::Start => {
+                if self.job.is_none() {
+                    self.start(ctx);
+                }
+            }
+            Msg::Cancel => {
+                if let Some(job) = &self.job {
+                    job.cancelled.set(true);
+                }
+            }
+            Msg::Progress(done) => match &mut self.job {
+                Some(job) => job.done = done,
+                None => return false,
+            },
+            Msg::Finished(result) => {
+                self.job = None;
+                match result {
+                    Ok(Some(bytes)) => download_bytes(
+                        &format!("overlay.{}", self.format.extension()),
+                        self.format.mime(),
+                        &bytes,
+                    ),
+                    Ok(None) => {}
+                    Err(e) => log::error!("Failed to export animation: {:?}", e),
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let busy = self.job.is_some();
+        let onchange_format = ctx.link().callback(|e: Event| {
+            let index = e
+                .target_unchecked_into::<HtmlSelectElement>()
+                .selected_index();
+            Msg::SetFormat(AnimationFormat::ALL[index.max(0) as usize])
+        });
+        let onchange_fps = ctx.link().batch_callback(|e: Event| {
+            let value = e
+                .target_unchecked_into::<HtmlInputElement>()
+                .value_as_number();
+            (value.is_finite() && 0. < value).then_some(Msg::SetFps(value as f32))
+        });
+        let onchange_width = ctx.link().batch_callback(|e: Event| {
+            let value = e
+                .target_unchecked_into::<HtmlInputElement>()
+                .value_as_number();
+            (value.is_finite() && 1. <= value && value <= u16::MAX as f64)
+                .then_some(Msg::SetWidth(value as u16))
+        });
+        let onchange_with_video = ctx.link().callback(|e: Event| {
+            Msg::SetWithVideo(e.target_unchecked_into::<HtmlInputElement>().checked())
+        });
+
+        html! {
+            <div class={css!(r#"
+                display: flex;
+                gap: 8px;
+                align-items: center;
+                margin-top: 8px;
+                font-size: 14px;
+
+                input[type="number"] {
+                    width: 64px;
+                }
+            "#)}>
+                <span>{ "Export animation" }</span>
+                <select disabled={busy} onchange={onchange_format}>
+                    { for AnimationFormat::ALL.iter().map(|format| html! {
+                        <option selected={*format == self.format}>{ format.label() }</option>
+                    }) }
+                </select>
+                <label>
+                    <input type="number" min="1" max="50" disabled={busy} value={ format!("{}", self.fps) } onchange={onchange_fps} />
+                    { " fps" }
+                </label>
+                <label>
+                    <input type="number" min="1" disabled={busy} value={ format!("{}", self.width) } onchange={onchange_width} />
+                    { " px" }
+                </label>
+                <label>
+                    <input type="checkbox" disabled={busy} checked={self.with_video} onchange={onchange_with_video} />
+                    { " With video" }
+                </label>
+                if let Some(job) = &self.job {
+                    { self.render_progress(ctx, job) }
+                } else {
+                    <button onclick={ctx.link().callback(|_| Msg::Start)}>{ "Export" }</button>
+                }
+            </div>
+        }
+    }
+}
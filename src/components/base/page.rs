@@ -1,6 +1,7 @@
 use crate::components::Title;
 use crate::prelude::*;
 use crate::routes::Route;
+use crate::shots::DEFAULT_SHOT;
 
 #[derive(Properties, PartialEq)]
 pub struct Props {
@@ -36,7 +37,8 @@ impl Component for Page {
               margin: 4px 14px 0;
             }
           "#)}>
-                <Link to={Route::Player}>{ "Player" }</Link>
+                <Link to={Route::Player { id: DEFAULT_SHOT.into() }}>{ "Player" }</Link>
+                <Link to={Route::Library}>{ "Library" }</Link>
                 <Link to={Route::About}>{ "About" }</Link>
               </div>
             </header>
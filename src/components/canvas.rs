@@ -0,0 +1,184 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{Axis, Overlay, Rect, AXIS_LABEL_SIZE, HUD_FONT_SIZE, TICK_LENGTH};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlVideoElement};
+use yew::NodeRef;
+
+use crate::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub overlay: Rc<Overlay>,
+}
+
+/// Draws the same overlay as the SVG backend onto a `<canvas>`.
+pub struct CanvasChart {
+    canvas: NodeRef,
+}
+
+impl CanvasChart {
+    fn context(&self) -> Option<CanvasRenderingContext2d> {
+        context_2d(&self.canvas.cast::<HtmlCanvasElement>()?)
+    }
+}
+
+/// Detached canvas, used to render exports off screen.
+pub fn create_canvas(width: u32, height: u32) -> Result<HtmlCanvasElement, JsValue> {
+    let canvas = gloo_utils::document()
+        .create_element("canvas")?
+        .dyn_into::<HtmlCanvasElement>()?;
+    canvas.set_width(width);
+    canvas.set_height(height);
+    Ok(canvas)
+}
+
+pub fn context_2d(canvas: &HtmlCanvasElement) -> Option<CanvasRenderingContext2d> {
+    canvas
+        .get_context("2d")
+        .ok()??
+        .dyn_into::<CanvasRenderingContext2d>()
+        .ok()
+}
+
+fn draw_axis(ctx: &CanvasRenderingContext2d, overlay: &Overlay) {
+    let Axis { from, to, ticks } = &overlay.axis;
+    ctx.set_line_width(1.0);
+    ctx.set_stroke_style(&JsValue::from_str(overlay.theme.axis));
+    ctx.begin_path();
+    ctx.move_to(from.0 as f64, from.1 as f64);
+    ctx.line_to(to.0 as f64, to.1 as f64);
+    for tick in ticks.iter() {
+        ctx.move_to(tick.x as f64, from.1 as f64);
+        ctx.line_to(tick.x as f64, (from.1 + TICK_LENGTH) as f64);
+    }
+    ctx.stroke();
+
+    ctx.set_fill_style(&JsValue::from_str(overlay.theme.text));
+    ctx.set_font(format!("{}px sans-serif", AXIS_LABEL_SIZE).as_str());
+    ctx.set_text_align("center");
+    for tick in ticks.iter() {
+        let y = from.1 + TICK_LENGTH + AXIS_LABEL_SIZE;
+        if let Err(e) = ctx.fill_text(&tick.label, tick.x as f64, y as f64) {
+            log::error!("Failed to draw label: {:?}", e);
+        }
+    }
+}
+
+fn draw_hud(ctx: &CanvasRenderingContext2d, overlay: &Overlay) {
+    ctx.set_font(format!("{}px sans-serif", HUD_FONT_SIZE).as_str());
+    ctx.set_text_align("start");
+    for line in overlay.hud.lines.iter() {
+        ctx.set_fill_style(&JsValue::from_str(overlay.theme.color(line.kind)));
+        let (x, y) = line.position;
+        if let Err(e) = ctx.fill_text(&line.text, x as f64, y as f64) {
+            log::error!("Failed to draw HUD: {:?}", e);
+        }
+    }
+}
+
+/// Draws `overlay` onto `ctx` without clearing it first, so it can go on top of a video frame.
+pub fn draw_overlay(ctx: &CanvasRenderingContext2d, overlay: &Overlay) {
+    ctx.save();
+    ctx.set_global_alpha(overlay.opacity as f64);
+
+    if let Some(panel) = &overlay.panel {
+        let Rect {
+            x,
+            y,
+            width,
+            height,
+        } = panel.rect;
+        ctx.set_fill_style(&JsValue::from_str(panel.fill));
+        ctx.begin_path();
+        if let Err(e) = ctx.round_rect_with_f64(
+            x as f64,
+            y as f64,
+            width as f64,
+            height as f64,
+            panel.radius as f64,
+        ) {
+            log::error!("Failed to draw panel: {:?}", e);
+        }
+        ctx.fill();
+    }
+
+    draw_axis(ctx, overlay);
+    draw_hud(ctx, overlay);
+
+    ctx.set_line_cap("round");
+    ctx.set_line_join("round");
+    for stroke in overlay.strokes.iter() {
+        if stroke.path.is_empty() {
+            continue;
+        }
+
+        ctx.set_global_alpha((overlay.opacity * stroke.opacity) as f64);
+        ctx.set_line_width(stroke.width as f64);
+        ctx.set_stroke_style(&JsValue::from_str(stroke.color));
+        ctx.begin_path();
+        for (i, (x, y)) in stroke.path.points.iter().enumerate() {
+            if i == 0 {
+                ctx.move_to(*x as f64, *y as f64);
+            } else {
+                ctx.line_to(*x as f64, *y as f64);
+            }
+        }
+        ctx.stroke();
+    }
+
+    ctx.restore();
+}
+
+/// Draws `overlay` scaled by `scale`, on top of the current picture of `video` if any.
+pub fn draw_frame(
+    ctx: &CanvasRenderingContext2d,
+    overlay: &Overlay,
+    video: Option<&HtmlVideoElement>,
+    scale: f64,
+) -> Result<(), JsValue> {
+    ctx.save();
+    ctx.scale(scale, scale)?;
+    if let Some(video) = video {
+        ctx.draw_image_with_html_video_element_and_dw_and_dh(
+            video,
+            0.,
+            0.,
+            overlay.size.0 as f64,
+            overlay.size.1 as f64,
+        )?;
+    }
+    draw_overlay(ctx, overlay);
+    ctx.restore();
+    Ok(())
+}
+
+impl Component for CanvasChart {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self {
+            canvas: NodeRef::default(),
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let (width, height) = ctx.props().overlay.size;
+        html! {
+            <canvas ref={self.canvas.clone()} width={ format!("{:.0}", width) } height={ format!("{:.0}", height) } />
+        }
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        let overlay = &ctx.props().overlay;
+        match self.context() {
+            Some(context) => {
+                let (width, height) = overlay.size;
+                context.clear_rect(0., 0., width as f64, height as f64);
+                draw_overlay(&context, overlay);
+            }
+            None => log::error!("Failed to get 2d context of canvas"),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{models::ShotData, Column, CsvTable, TimeUnit};
+use web_sys::HtmlSelectElement;
+use yew::{use_state, AttrValue, Callback, Event, TargetCast};
+
+use crate::prelude::*;
+
+/// Rows shown to help tell the columns apart.
+const PREVIEW_ROWS: usize = 5;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    /// Name of the CSV file, used as the title of the shot.
+    pub name: AttrValue,
+    pub table: Rc<CsvTable>,
+    pub onimport: Callback<ShotData>,
+    pub oncancel: Callback<()>,
+}
+
+fn selected_index(e: &Event) -> usize {
+    e.target_unchecked_into::<HtmlSelectElement>()
+        .selected_index() as usize
+}
+
+/// Asks which series each column of a CSV file holds, starting from a guess
+/// made from the headers, before the file is imported as a shot.
+#[function_component(ColumnMapping)]
+pub fn column_mapping(props: &Props) -> Html {
+    let mapping = use_state(|| props.table.guess_mapping());
+    let time_unit = use_state(|| props.table.guess_time_unit(&mapping));
+    let error = use_state(|| None::<String>);
+
+    let onchange_column = |i: usize| {
+        let mapping = mapping.clone();
+        Callback::from(move |e: Event| {
+            // The first option leaves the column out.
+            let column = selected_index(&e)
+                .checked_sub(1)
+                .and_then(|index| Column::ALL.get(index).copied());
+            let mut next = (*mapping).clone();
+            // A series is read from one column only.
+            for other in next.iter_mut() {
+                if column.is_some() && *other == column {
+                    *other = None;
+                }
+            }
+            next[i] = column;
+            mapping.set(next);
+        })
+    };
+    let onchange_time_unit = {
+        let time_unit = time_unit.clone();
+        Callback::from(move |e: Event| {
+            if let Some(unit) = TimeUnit::ALL.get(selected_index(&e)) {
+                time_unit.set(*unit);
+            }
+        })
+    };
+    let onimport = {
+        let table = props.table.clone();
+        let name = props.name.clone();
+        let onimport = props.onimport.clone();
+        let mapping = mapping.clone();
+        let time_unit = time_unit.clone();
+        let error = error.clone();
+        Callback::from(move |_| match table.to_shot(&name, &mapping, *time_unit) {
+            Ok(data) => onimport.emit(data),
+            Err(e) => error.set(Some(e)),
+        })
+    };
+    let oncancel = props.oncancel.reform(|_| ());
+
+    let render_select = |i: usize, selected: Option<Column>| {
+        // Keyed by the selection so that a column cleared by another one is
+        // redrawn, as `selected` only sets the initial option.
+        html! {
+            <select key={format!("{:?}", selected)} onchange={onchange_column(i)}>
+                <option selected={selected.is_none()}>{ "(skip)" }</option>
+                { for Column::ALL.iter().map(|column| html! {
+                    <option selected={selected == Some(*column)}>{ column.label() }</option>
+                }) }
+            </select>
+        }
+    };
+
+    html! {
+        <div class={css!(r#"
+            margin-bottom: 8px;
+            padding: 8px;
+            border: 1px solid #c0c0c0;
+            font-size: 14px;
+            overflow-x: auto;
+
+            table {
+                border-collapse: collapse;
+            }
+
+            td, th {
+                padding: 2px 8px;
+                text-align: left;
+                white-space: nowrap;
+            }
+
+            td {
+                color: #606060;
+            }
+        "#)}>
+            <div>{ format!("Which series does each column of {} hold?", props.name) }</div>
+            <table>
+                <tr>
+                    { for props.table.headers.iter().map(|header| html! { <th>{ header }</th> }) }
+                </tr>
+                <tr>
+                    { for mapping.iter().enumerate().map(|(i, column)| html! { <th>{ render_select(i, *column) }</th> }) }
+                </tr>
+                { for props.table.rows.iter().take(PREVIEW_ROWS).map(|row| html! {
+                    <tr>
+                        { for row.iter().map(|cell| html! { <td>{ cell }</td> }) }
+                    </tr>
+                }) }
+            </table>
+            <div class={css!(r#"
+                display: flex;
+                gap: 8px;
+                align-items: center;
+                margin-top: 8px;
+            "#)}>
+                <label>
+                    { "Time in " }
+                    <select onchange={onchange_time_unit}>
+                        { for TimeUnit::ALL.iter().map(|unit| html! {
+                            <option selected={*unit == *time_unit}>{ unit.label() }</option>
+                        }) }
+                    </select>
+                </label>
+                <button onclick={onimport}>{ "Import" }</button>
+                <button onclick={oncancel}>{ "Skip" }</button>
+                if let Some(error) = &*error {
+                    <span>{ error }</span>
+                }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,46 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{models::ShotData, to_csv, CsvLayout};
+use yew::Callback;
+
+use crate::prelude::*;
+use crate::web::download_bytes;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub data: Rc<ShotData>,
+}
+
+fn file_name(layout: CsvLayout) -> &'static str {
+    match layout {
+        CsvLayout::Tidy => "shot.csv",
+        CsvLayout::Long => "shot-long.csv",
+    }
+}
+
+/// Downloads the time series of the shot for pandas or a spreadsheet.
+#[function_component(ExportCsv)]
+pub fn export_csv(props: &Props) -> Html {
+    let onclick = |layout: CsvLayout| {
+        let data = props.data.clone();
+        Callback::from(move |_| {
+            let csv = to_csv(&data, layout);
+            download_bytes(file_name(layout), "text/csv", csv.as_bytes());
+        })
+    };
+
+    html! {
+        <div class={css!(r#"
+            display: flex;
+            gap: 8px;
+            align-items: center;
+            margin-top: 8px;
+            font-size: 14px;
+        "#)}>
+            <span>{ "Export CSV" }</span>
+            { for CsvLayout::ALL.iter().map(|layout| html! {
+                <button onclick={onclick(*layout)}>{ layout.label() }</button>
+            }) }
+        </div>
+    }
+}
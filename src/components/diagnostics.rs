@@ -0,0 +1,60 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{models::ShotData, validate, Diagnostic, Severity};
+
+use crate::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub data: Rc<ShotData>,
+}
+
+fn color(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "#606060",
+        Severity::Warning => "#b06000",
+        Severity::Error => "#c00000",
+    }
+}
+
+fn render_diagnostic(diagnostic: &Diagnostic) -> Html {
+    html! {
+        <li>
+            <strong style={ format!("color: {}", color(diagnostic.severity)) }>
+                { diagnostic.severity.label() }
+            </strong>
+            { format!(" {}", diagnostic.message) }
+            if let Some(t) = diagnostic.t {
+                { format!(" (at {:.1} s)", t) }
+            }
+        </li>
+    }
+}
+
+/// Problems [`validate`] finds in the shot, e.g. series that don't line up.
+#[function_component(Diagnostics)]
+pub fn diagnostics(props: &Props) -> Html {
+    let diagnostics = validate(&props.data);
+    html! {
+        <details open={ diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error) } class={css!(r#"
+            margin: 8px 0;
+            font-size: 14px;
+
+            ul {
+                margin: 4px 0;
+                padding-left: 20px;
+            }
+        "#)}>
+            <summary>
+                if diagnostics.is_empty() {
+                    { "Checks: no problems found" }
+                } else {
+                    { format!("Checks: {} found", diagnostics.len()) }
+                }
+            </summary>
+            <ul>
+                { for diagnostics.iter().map(render_diagnostic) }
+            </ul>
+        </details>
+    }
+}
@@ -0,0 +1,125 @@
+use std::rc::Rc;
+
+use decent_overlay_core::Overlay;
+use wasm_bindgen::JsValue;
+use web_sys::{HtmlInputElement, HtmlVideoElement};
+use yew::{use_state, Callback, Event, NodeRef, TargetCast};
+
+use crate::components::canvas::{context_2d, create_canvas, draw_frame};
+use crate::prelude::*;
+use crate::web::{download_bytes, download_url};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub overlay: Rc<Overlay>,
+    pub video: NodeRef,
+    /// Seconds into the shot, used to name the files.
+    pub t: f32,
+}
+
+/// Current picture of `video` as a PNG data URL, at the video's own resolution.
+fn capture_video(video: &HtmlVideoElement) -> Result<String, JsValue> {
+    let canvas = create_canvas(video.video_width(), video.video_height())?;
+    let ctx = context_2d(&canvas).ok_or("Missing 2d context")?;
+    ctx.draw_image_with_html_video_element(video, 0., 0.)?;
+    canvas.to_data_url_with_type("image/png")
+}
+
+/// Renders `overlay` scaled to `width` pixels, optionally over the current video frame.
+fn rasterize(
+    overlay: &Overlay,
+    video: Option<&HtmlVideoElement>,
+    width: u32,
+) -> Result<String, JsValue> {
+    let scale = width as f64 / overlay.size.0 as f64;
+    let height = (overlay.size.1 as f64 * scale).round() as u32;
+    let canvas = create_canvas(width, height)?;
+    let ctx = context_2d(&canvas).ok_or("Missing 2d context")?;
+    draw_frame(&ctx, overlay, video, scale)?;
+    canvas.to_data_url_with_type("image/png")
+}
+
+#[function_component(ExportFrame)]
+pub fn export_frame(props: &Props) -> Html {
+    let width = use_state(|| 1080u32);
+    let with_video = use_state(|| false);
+
+    let onclick_svg = {
+        let overlay = props.overlay.clone();
+        let video = with_video.then(|| props.video.clone());
+        let filename = format!("overlay-{:.1}s.svg", props.t);
+        Callback::from(move |_| {
+            let background = match video.as_ref().and_then(|v| v.cast::<HtmlVideoElement>()) {
+                Some(video) => match capture_video(&video) {
+                    Ok(href) => Some(href),
+                    Err(e) => {
+                        log::error!("Failed to capture video frame: {:?}", e);
+                        return;
+                    }
+                },
+                None => None,
+            };
+            let svg = overlay.to_svg(background.as_deref());
+            download_bytes(&filename, "image/svg+xml", svg.as_bytes());
+        })
+    };
+
+    let onclick_png = {
+        let overlay = props.overlay.clone();
+        let video = with_video.then(|| props.video.clone());
+        let width = *width;
+        let filename = format!("overlay-{:.1}s.png", props.t);
+        Callback::from(move |_| {
+            let video = video.as_ref().and_then(|v| v.cast::<HtmlVideoElement>());
+            match rasterize(&overlay, video.as_ref(), width) {
+                Ok(url) => download_url(&filename, &url),
+                Err(e) => log::error!("Failed to rasterize overlay: {:?}", e),
+            }
+        })
+    };
+
+    let onchange_width = {
+        let width = width.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<HtmlInputElement>()
+                .value_as_number();
+            if value.is_finite() && 0. < value {
+                width.set(value as u32);
+            }
+        })
+    };
+
+    let onchange_with_video = {
+        let with_video = with_video.clone();
+        Callback::from(move |e: Event| {
+            with_video.set(e.target_unchecked_into::<HtmlInputElement>().checked());
+        })
+    };
+
+    html! {
+        <div class={css!(r#"
+            display: flex;
+            gap: 8px;
+            align-items: center;
+            margin-top: 8px;
+            font-size: 14px;
+
+            input[type="number"] {
+                width: 64px;
+            }
+        "#)}>
+            <span>{ "Export frame" }</span>
+            <label>
+                <input type="checkbox" checked={*with_video} onchange={onchange_with_video} />
+                { " With video" }
+            </label>
+            <button onclick={onclick_svg}>{ "SVG" }</button>
+            <label>
+                <input type="number" min="1" value={ format!("{}", *width) } onchange={onchange_width} />
+                { " px" }
+            </label>
+            <button onclick={onclick_png}>{ "PNG" }</button>
+        </div>
+    }
+}
@@ -0,0 +1,154 @@
+use decent_overlay_core::{BrewTarget, Extraction};
+use web_sys::HtmlInputElement;
+use yew::{Callback, InputEvent, TargetCast};
+
+use crate::prelude::*;
+
+/// Size of the brew control chart.
+const CHART_SIZE: (f32, f32) = (320.0, 240.0);
+/// Room left of and below the chart for the axis labels.
+const CHART_MARGIN: f32 = 32.0;
+/// Extraction yield (%) shown on the chart unless the shot falls outside.
+const EY_AXIS: (f32, f32) = (14.0, 26.0);
+/// Strength (% TDS) shown on the chart unless the shot falls outside.
+const TDS_AXIS: (f32, f32) = (6.0, 14.0);
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub extraction: Extraction,
+    pub onchange: Callback<Extraction>,
+}
+
+/// Axis bounds widened to whole steps of 2 around `value`.
+fn axis((min, max): (f32, f32), value: Option<f32>) -> (f32, f32) {
+    match value {
+        Some(value) => (
+            min.min((value / 2.).floor() * 2.),
+            max.max((value / 2.).ceil() * 2.),
+        ),
+        None => (min, max),
+    }
+}
+
+/// Strength against extraction, with the target box, the line of the brew
+/// ratio and the shot on it.
+fn render_chart(extraction: &Extraction, target: &BrewTarget) -> Html {
+    let ey = extraction.ey();
+    let (ey_min, ey_max) = axis(EY_AXIS, ey);
+    let (tds_min, tds_max) = axis(TDS_AXIS, ey.map(|_| extraction.tds));
+    let (width, height) = CHART_SIZE;
+    let x = |ey: f32| CHART_MARGIN + (ey - ey_min) / (ey_max - ey_min) * (width - CHART_MARGIN);
+    let y = |tds: f32| (tds_max - tds) / (tds_max - tds_min) * (height - CHART_MARGIN);
+    let ticks = |min: f32, max: f32| (min as i32..=max as i32).step_by(2).map(|v| v as f32);
+
+    html! {
+        <svg width={ format!("{}", width) } height={ format!("{}", height) } viewBox={ format!("0 0 {} {}", width, height) } xmlns="http://www.w3.org/2000/svg">
+            <rect
+                x={ format!("{}", x(*target.ey.start())) }
+                y={ format!("{}", y(*target.tds.end())) }
+                width={ format!("{}", x(*target.ey.end()) - x(*target.ey.start())) }
+                height={ format!("{}", y(*target.tds.start()) - y(*target.tds.end())) }
+                fill="#e0f0e0"
+                stroke="#60a060"
+            />
+            <g stroke="#c0c0c0" stroke-width="1px">
+                <line x1={ format!("{}", CHART_MARGIN) } y1="0" x2={ format!("{}", CHART_MARGIN) } y2={ format!("{}", height - CHART_MARGIN) } />
+                <line x1={ format!("{}", CHART_MARGIN) } y1={ format!("{}", height - CHART_MARGIN) } x2={ format!("{}", width) } y2={ format!("{}", height - CHART_MARGIN) } />
+            </g>
+            <g fill="#606060" font-size="10px" font-family="sans-serif">
+                <g text-anchor="middle">
+                    { for ticks(ey_min, ey_max).map(|ey| html! {
+                        <text x={ format!("{}", x(ey)) } y={ format!("{}", height - CHART_MARGIN + 12.) }>{ format!("{}", ey) }</text>
+                    }) }
+                    <text x={ format!("{}", (CHART_MARGIN + width) / 2.) } y={ format!("{}", height - 4.) }>{ "Extraction yield (%)" }</text>
+                </g>
+                <g text-anchor="end">
+                    { for ticks(tds_min, tds_max).map(|tds| html! {
+                        <text x={ format!("{}", CHART_MARGIN - 4.) } y={ format!("{}", y(tds) + 3.) }>{ format!("{}", tds) }</text>
+                    }) }
+                </g>
+                <text x="0" y="10">{ "TDS (%)" }</text>
+            </g>
+            if let Some(ratio) = extraction.ratio() {
+                // Every strength and yield a shot of this ratio can reach.
+                <line
+                    x1={ format!("{}", x(ey_min)) }
+                    y1={ format!("{}", y(ey_min / ratio)) }
+                    x2={ format!("{}", x(ey_max)) }
+                    y2={ format!("{}", y(ey_max / ratio)) }
+                    stroke="#a0a0ff"
+                    stroke-dasharray="4 4"
+                />
+            }
+            if let Some(ey) = ey {
+                <circle cx={ format!("{}", x(ey)) } cy={ format!("{}", y(extraction.tds)) } r="5" fill="#c00000" />
+            }
+        </svg>
+    }
+}
+
+/// Computes the extraction yield from dose, beverage weight and a TDS reading
+/// and plots it on a brew control chart.
+#[function_component(ExtractionPanel)]
+pub fn extraction_panel(props: &Props) -> Html {
+    let extraction = props.extraction;
+    let target = BrewTarget::espresso();
+    let field =
+        |label: &'static str, value: f32, step: &'static str, set: fn(&mut Extraction, f32)| {
+            let onchange = props.onchange.clone();
+            let oninput = Callback::from(move |e: InputEvent| {
+                let value = e
+                    .target_unchecked_into::<HtmlInputElement>()
+                    .value_as_number() as f32;
+                let mut extraction = extraction;
+                set(&mut extraction, if value.is_finite() { value } else { 0. });
+                onchange.emit(extraction);
+            });
+            html! {
+                <label>
+                    { label }
+                    <input type="number" min="0" {step} value={ format!("{}", value) } {oninput} />
+                </label>
+            }
+        };
+
+    html! {
+        <details class={css!(r#"
+            margin: 8px 0;
+            font-size: 14px;
+
+            .fields {
+                display: flex;
+                flex-wrap: wrap;
+                gap: 16px;
+                align-items: center;
+                margin: 8px 0;
+            }
+
+            input[type="number"] {
+                width: 64px;
+                margin-left: 4px;
+            }
+        "#)}>
+            <summary>
+                { "Extraction" }
+                if let Some(ey) = extraction.ey() {
+                    { format!(": {:.1} % EY, {}", ey, target.describe(extraction.tds, ey)) }
+                }
+            </summary>
+            <div class="fields">
+                { field("Dose (g)", extraction.dose, "0.1", |e, v| e.dose = v) }
+                { field("Beverage (g)", extraction.beverage, "0.1", |e, v| e.beverage = v) }
+                { field("TDS (%)", extraction.tds, "0.01", |e, v| e.tds = v) }
+                <span>
+                    if let Some(ey) = extraction.ey() {
+                        { format!("EY {:.2} %", ey) }
+                    } else {
+                        { "Enter dose, beverage and TDS to compute the yield" }
+                    }
+                </span>
+            </div>
+            { render_chart(&extraction, &target) }
+        </details>
+    }
+}
@@ -0,0 +1,181 @@
+use decent_overlay_core::{EncodingPreset, FfmpegJob, OverlaySource, Range, Settings};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::{use_state, Callback, Event, TargetCast, UseStateHandle};
+
+use crate::prelude::*;
+use crate::web::copy_text;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub time_span: Range,
+    pub settings: Settings,
+    /// Intrinsic size of the loaded video.
+    pub video_size: (u32, u32),
+}
+
+#[derive(Clone, PartialEq)]
+struct Form {
+    video: String,
+    output: String,
+    ass: bool,
+    fps: f32,
+    preset: EncodingPreset,
+    script: bool,
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Self {
+            video: "shot.mp4".into(),
+            output: "shot-overlay.mp4".into(),
+            ass: false,
+            fps: 30.,
+            preset: EncodingPreset::default(),
+            script: false,
+        }
+    }
+}
+
+/// Builds a callback that applies `f` to a copy of the form and stores it.
+fn update<E: 'static>(
+    form: &UseStateHandle<Form>,
+    f: impl Fn(&mut Form, E) + 'static,
+) -> Callback<E> {
+    let form = form.clone();
+    Callback::from(move |e: E| {
+        let mut next = (*form).clone();
+        f(&mut next, e);
+        form.set(next);
+    })
+}
+
+fn text_value(e: &Event) -> String {
+    e.target_unchecked_into::<HtmlInputElement>().value()
+}
+
+fn number_value(e: &Event) -> Option<f32> {
+    let value = e
+        .target_unchecked_into::<HtmlInputElement>()
+        .value_as_number() as f32;
+    (value.is_finite() && 0. < value).then_some(value)
+}
+
+fn checked(e: &Event) -> bool {
+    e.target_unchecked_into::<HtmlInputElement>().checked()
+}
+
+/// Shows an ffmpeg command that composites the exported overlay over the footage.
+#[function_component(FfmpegCommand)]
+pub fn ffmpeg_command(props: &Props) -> Html {
+    let form = use_state(Form::default);
+
+    let job = FfmpegJob {
+        video: form.video.clone(),
+        output: form.output.clone(),
+        source: if form.ass {
+            OverlaySource::Ass {
+                path: "frames/overlay.ass".into(),
+            }
+        } else {
+            OverlaySource::Frames {
+                pattern: "frames/frame_%05d.png".into(),
+                fps: form.fps,
+            }
+        },
+        time_span: props.time_span.clone(),
+        offset: props.settings.offset,
+        trim: props.settings.trim,
+        size: props.video_size,
+        preset: form.preset,
+    };
+    let text = if form.script {
+        job.script()
+    } else {
+        job.command()
+    };
+
+    let onchange_video = update(&form, |form, e: Event| form.video = text_value(&e));
+    let onchange_output = update(&form, |form, e: Event| form.output = text_value(&e));
+    let onchange_ass = update(&form, |form, e: Event| form.ass = checked(&e));
+    let onchange_fps = update(&form, |form, e: Event| {
+        if let Some(fps) = number_value(&e) {
+            form.fps = fps;
+        }
+    });
+    let onchange_preset = update(&form, |form, e: Event| {
+        let index = e
+            .target_unchecked_into::<HtmlSelectElement>()
+            .selected_index() as usize;
+        if let Some(preset) = EncodingPreset::ALL.get(index) {
+            form.preset = *preset;
+        }
+    });
+    let onchange_script = update(&form, |form, e: Event| form.script = checked(&e));
+
+    let onclick_copy = {
+        let text = text.clone();
+        Callback::from(move |_| {
+            if let Err(e) = copy_text(&text) {
+                log::error!("Failed to copy command: {:?}", e);
+            }
+        })
+    };
+
+    html! {
+        <div class={css!(r#"
+            margin-top: 8px;
+            font-size: 14px;
+
+            div {
+                display: flex;
+                flex-wrap: wrap;
+                gap: 8px;
+                align-items: center;
+            }
+
+            input[type="number"] {
+                width: 48px;
+            }
+
+            pre {
+                white-space: pre-wrap;
+                word-break: break-all;
+                padding: 8px;
+                background: #f4f4f4;
+            }
+        "#)}>
+            <div>
+                <span>{ "ffmpeg" }</span>
+                <label>
+                    { "Video " }
+                    <input type="text" value={form.video.clone()} onchange={onchange_video} />
+                </label>
+                <label>
+                    { "Output " }
+                    <input type="text" value={form.output.clone()} onchange={onchange_output} />
+                </label>
+                <label>
+                    <input type="checkbox" checked={form.ass} onchange={onchange_ass} />
+                    { " From ASS" }
+                </label>
+                if !form.ass {
+                    <label>
+                        <input type="number" min="1" value={ format!("{}", form.fps) } onchange={onchange_fps} />
+                        { " fps" }
+                    </label>
+                }
+                <select onchange={onchange_preset}>
+                    { for EncodingPreset::ALL.iter().map(|preset| html! {
+                        <option selected={*preset == form.preset}>{ preset.label() }</option>
+                    }) }
+                </select>
+                <label>
+                    <input type="checkbox" checked={form.script} onchange={onchange_script} />
+                    { " Shell script" }
+                </label>
+                <button onclick={onclick_copy}>{ "Copy" }</button>
+            </div>
+            <pre>{ text }</pre>
+        </div>
+    }
+}
@@ -1,21 +1,47 @@
 use std::rc::Rc;
 
+use decent_overlay_core::{
+    models::{ChartData, SeriesKind, ShotData},
+    Alignment, Backend, Comparison, Overlay, Range, Rect, Settings, Units,
+};
 use gloo::timers::callback::Interval;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{HtmlMediaElement, HtmlVideoElement, ResizeObserver};
+use yew::{AttrValue, Callback, NodeRef};
 
-use crate::libs::Range;
-use crate::prelude::*;
-use crate::{
-    components::Chart,
-    libs::models::{
-        json::{ShotDataJson, SHOT2},
-        ChartData, ShotData,
-    },
+use crate::components::{
+    CanvasChart, ExportAnimation, ExportCsv, ExportFrame, ExportSubtitles, FfmpegCommand,
+    OverlaySvg,
 };
+use crate::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub data: Rc<ShotData>,
+    pub video_src: AttrValue,
+    pub settings: Settings,
+    /// Seconds into the shot to open paused at, e.g. from a shared link.
+    #[prop_or_default]
+    pub start: f32,
+    /// Reports the position whenever the timer moves.
+    #[prop_or_default]
+    pub ontime: Callback<f32>,
+    /// Shot drawn under this one for comparison.
+    #[prop_or_default]
+    pub reference: Option<Rc<ShotData>>,
+    #[prop_or_default]
+    pub alignment: Alignment,
+}
 
 pub enum Msg {
     PlayOrPause,
     Stop,
     Tick,
+    Resize,
+    /// The video can show frames.
+    Loaded,
+    /// The video moved, e.g. with its own controls.
+    Seeked,
 }
 
 pub enum State {
@@ -44,7 +70,15 @@ impl TimerState {
         js_sys::Date::now()
     }
 
-    pub fn into_pause(&self) -> Self {
+    /// Stands at `elapsed` ms from now on.
+    fn at(elapsed: f64) -> Self {
+        Self {
+            ticked: Self::get_timestamp(),
+            elapsed,
+        }
+    }
+
+    pub fn pause(&self) -> Self {
         let t = Self::get_timestamp();
         Self {
             ticked: t,
@@ -52,7 +86,7 @@ impl TimerState {
         }
     }
 
-    pub fn into_playing(&self) -> Self {
+    pub fn resume(&self) -> Self {
         Self {
             ticked: Self::get_timestamp(),
             elapsed: self.elapsed,
@@ -71,18 +105,33 @@ impl TimerState {
 pub struct Graph {
     state: State,
     handle: Option<Interval>,
-    data: ShotData,
+    data: Rc<ShotData>,
+    units: Units,
     time_span: Range,
-    pressure_data: Rc<ChartData>,
-    temp_basket_data: Rc<ChartData>,
-    temp_mix_data: Rc<ChartData>,
-    flow_data: Rc<ChartData>,
-    flow_by_weight_data: Rc<ChartData>,
+    charts: Rc<Vec<(SeriesKind, Rc<ChartData>)>>,
+    /// Series of the reference shot, lined up with `charts`.
+    reference: Rc<Vec<(SeriesKind, Rc<ChartData>)>>,
+    video: NodeRef,
+    frame: Rect,
+    observer: Option<(ResizeObserver, Closure<dyn Fn()>)>,
 }
 
-pub const INNER: (f32, f32) = (400.0, 660.0);
+/// Frame size used until the video reports its own dimensions.
+const INNER: (f32, f32) = (400.0, 660.0);
 const TIMER_DURATION: u32 = 75;
 
+/// Series of the shot and of the reference, if any, on shared axes.
+fn charts_of(data: &ShotData, props: &Props, units: &Units) -> Comparison {
+    match &props.reference {
+        Some(reference) => Comparison::new(data, reference, units, props.alignment),
+        None => Comparison {
+            charts: ChartData::all_in(data, units),
+            reference: vec![],
+            offset: 0.,
+        },
+    }
+}
+
 impl Graph {
     fn is_playing(&self) -> bool {
         matches!(self.state, State::Playing(_))
@@ -96,9 +145,94 @@ impl Graph {
         }
     }
 
+    /// Position in the shot, in seconds.
+    fn t(&self) -> f32 {
+        (self.elapsed() * 0.001) as f32
+    }
+
+    /// The video, once it knows its duration, so that it can set the time.
+    fn loaded_video(&self) -> Option<HtmlVideoElement> {
+        self.video
+            .cast::<HtmlVideoElement>()
+            .filter(|video| HtmlMediaElement::HAVE_METADATA <= video.ready_state())
+    }
+
+    /// Moves the video to the frame shown with `t` seconds into the shot.
+    fn seek_video(&self, ctx: &Context<Self>, t: f32) {
+        if let Some(video) = self.loaded_video() {
+            video.set_current_time((t + ctx.props().settings.offset).max(0.) as f64);
+        }
+    }
+
+    /// Milliseconds into the shot of the frame the video shows, as the exports
+    /// line them up.
+    fn video_elapsed(&self, ctx: &Context<Self>) -> Option<f64> {
+        let video = self.loaded_video()?;
+        Some((video.current_time() - ctx.props().settings.offset as f64) * 1000.)
+    }
+
+    /// Box of the rendered video picture inside the `<video>` element, letterboxing excluded.
+    fn measure_frame(&self) -> Option<Rect> {
+        let video = self.video.cast::<HtmlVideoElement>()?;
+        let container = (video.client_width() as f32, video.client_height() as f32);
+        let intrinsic = (video.video_width() as f32, video.video_height() as f32);
+        if intrinsic.0 == 0. || intrinsic.1 == 0. {
+            return None;
+        }
+        Some(Rect::contain(intrinsic, container))
+    }
+
+    /// Intrinsic size of the video, or of the fallback frame until it is known.
+    fn video_size(&self) -> (u32, u32) {
+        self.video
+            .cast::<HtmlVideoElement>()
+            .map(|video| (video.video_width(), video.video_height()))
+            .filter(|(width, height)| 0 < *width && 0 < *height)
+            .unwrap_or((INNER.0 as u32, INNER.1 as u32))
+    }
+
+    fn observe_resize(&mut self, ctx: &Context<Self>) {
+        let Some(video) = self.video.cast::<HtmlVideoElement>() else {
+            return;
+        };
+
+        let link = ctx.link().clone();
+        let callback = Closure::<dyn Fn()>::new(move || link.send_message(Msg::Resize));
+        match ResizeObserver::new(callback.as_ref().unchecked_ref()) {
+            Ok(observer) => {
+                observer.observe(&video);
+                self.observer = Some((observer, callback));
+            }
+            Err(e) => log::error!("Failed to observe video size: {:?}", e),
+        }
+    }
+
+    fn overlay(&self, ctx: &Context<Self>) -> Overlay {
+        Overlay::build_compared(
+            &self.charts,
+            &self.reference,
+            &self.units,
+            &self.time_span,
+            self.t(),
+            self.frame.size(),
+            &ctx.props().settings,
+        )
+    }
+
+    fn render_overlay(&self, ctx: &Context<Self>, overlay: Rc<Overlay>) -> Html {
+        match ctx.props().settings.backend {
+            Backend::Svg => html! {
+                <OverlaySvg {overlay} />
+            },
+            Backend::Canvas => html! {
+                <CanvasChart {overlay} />
+            },
+        }
+    }
+
     fn render_timer(&self) -> String {
         match &self.state {
-            State::Stopped => format!("Elapsed: -"),
+            State::Stopped => "Elapsed: -".to_string(),
             State::Playing(ts) => format!("Elapsed: {} ms", ts.elapsed),
             State::Paused(ts) => format!("Elapsed: {} ms", ts.elapsed),
         }
@@ -107,30 +241,73 @@ impl Graph {
 
 impl Component for Graph {
     type Message = Msg;
-    type Properties = ();
+    type Properties = Props;
 
-    fn create(_: &Context<Self>) -> Self {
-        let data: ShotData = serde_json::from_str::<ShotDataJson>(SHOT2).unwrap().into();
+    fn create(ctx: &Context<Self>) -> Self {
+        let data = ctx.props().data.clone();
+        let units = ctx
+            .props()
+            .settings
+            .units
+            .unwrap_or_else(|| Units::from_shot(&data));
         let time_span = Range::from_series(&data.elapsed);
-        let pressure_data = Rc::new(ChartData::pressure(&data));
-        let temp_basket_data = Rc::new(ChartData::temp_basket(&data));
-        let temp_mix_data = Rc::new(ChartData::temp_mix(&data));
-        let flow_data = Rc::new(ChartData::flow(&data));
-        let flow_by_weight_data = Rc::new(ChartData::flow_by_weight(&data));
+        let comparison = charts_of(&data, ctx.props(), &units);
+        let start = ctx.props().start;
+        let state = if 0. < start {
+            State::Paused(TimerState {
+                elapsed: start as f64 * 1000.,
+                ..Default::default()
+            })
+        } else {
+            State::Stopped
+        };
 
         Self {
-            state: State::Stopped,
+            state,
             handle: None,
             data,
+            units,
             time_span,
-            pressure_data,
-            temp_basket_data,
-            temp_mix_data,
-            flow_data,
-            flow_by_weight_data,
+            charts: Rc::new(comparison.charts),
+            reference: Rc::new(comparison.reference),
+            video: NodeRef::default(),
+            frame: Rect::new(0., 0., INNER.0, INNER.1),
+            observer: None,
         }
     }
 
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        // Editing the logged values, e.g. in the extraction calculator, keeps
+        // the position and the charts.
+        let shot_changed = !Rc::ptr_eq(&ctx.props().data, &old_props.data)
+            && !ctx.props().data.same_samples(&old_props.data);
+        self.data = ctx.props().data.clone();
+        if shot_changed {
+            self.time_span = Range::from_series(&self.data.elapsed);
+            self.state = State::Stopped;
+            self.handle = None;
+        }
+
+        let units = ctx
+            .props()
+            .settings
+            .units
+            .unwrap_or_else(|| Units::from_shot(&self.data));
+        let props = ctx.props();
+        let reference_changed = match (&props.reference, &old_props.reference) {
+            (Some(reference), Some(old)) => !Rc::ptr_eq(reference, old),
+            (None, None) => false,
+            _ => true,
+        } || props.alignment != old_props.alignment;
+        if shot_changed || reference_changed || units != self.units {
+            self.units = units;
+            let comparison = charts_of(&self.data, props, &self.units);
+            self.charts = Rc::new(comparison.charts);
+            self.reference = Rc::new(comparison.reference);
+        }
+        true
+    }
+
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::PlayOrPause => {
@@ -144,33 +321,75 @@ impl Component for Graph {
                         self.handle = Some(tick_handle);
                     }
                     State::Playing(ts) => {
-                        self.state = State::Paused(ts.into_pause());
+                        self.state = State::Paused(ts.pause());
                         self.handle = None;
+                        ctx.props().ontime.emit(self.t());
                     }
                     State::Paused(ts) => {
-                        self.state = State::Playing(ts.into_playing());
+                        self.state = State::Playing(ts.resume());
                         self.handle = Some(tick_handle);
                     }
                 }
+                if let Some(video) = self.loaded_video() {
+                    if self.is_playing() {
+                        self.seek_video(ctx, self.t());
+                        let _ = video.play();
+                    } else {
+                        let _ = video.pause();
+                    }
+                }
                 true
             }
             Msg::Stop => {
                 self.state = State::Stopped;
                 self.handle = None;
+                if let Some(video) = self.loaded_video() {
+                    let _ = video.pause();
+                }
+                self.seek_video(ctx, 0.);
+                ctx.props().ontime.emit(self.t());
                 true
             }
             Msg::Tick => {
                 if let State::Playing(ts) = &self.state {
-                    self.state = State::Playing(ts.tick());
+                    // The video sets the pace whenever there is one.
+                    self.state = State::Playing(match self.video_elapsed(ctx) {
+                        Some(elapsed) => TimerState::at(elapsed),
+                        None => ts.tick(),
+                    });
+                    ctx.props().ontime.emit(self.t());
                     true
                 } else {
                     false
                 }
             }
+            Msg::Loaded => {
+                if !self.is_playing() {
+                    self.seek_video(ctx, self.t());
+                }
+                false
+            }
+            // A paused overlay follows the video when it is moved by hand.
+            Msg::Seeked => match (&self.state, self.video_elapsed(ctx)) {
+                (State::Paused(_), Some(elapsed)) if elapsed != self.elapsed() => {
+                    self.state = State::Paused(TimerState::at(elapsed));
+                    ctx.props().ontime.emit(self.t());
+                    true
+                }
+                _ => false,
+            },
+            Msg::Resize => match self.measure_frame() {
+                Some(frame) if frame != self.frame => {
+                    self.frame = frame;
+                    true
+                }
+                _ => false,
+            },
         }
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let overlay = Rc::new(self.overlay(ctx));
         html! {
             <>
                 <div>
@@ -181,23 +400,54 @@ impl Component for Graph {
                 <div class={css!(r#"
                     position: relative;
                 "#)}>
-                    <video controls=true width="400">
-                        <source src="http://localhost:8888/shot2.mp4" type="video/mp4" />
-                    </video>
-                    <div class={css!(r#"
+                    <video ref={self.video.clone()} src={ctx.props().video_src.clone()} controls=true onloadedmetadata={ctx.link().callback(|_| Msg::Resize)} onloadeddata={ctx.link().callback(|_| Msg::Loaded)} onseeked={ctx.link().callback(|_| Msg::Seeked)} class={css!(r#"
+                        display: block;
+                        width: 100%;
+                        max-height: 80vh;
+                        object-fit: contain;
+                    "#)} />
+                    <div style={ format!("left: {}px; top: {}px;", self.frame.x, self.frame.y) } class={css!(r#"
                         position: absolute;
-                        top: 0;
+                        pointer-events: none;
                     "#)}>
-                        <svg width={ format!("{}", INNER.0) } height={ format!("{}", INNER.1) } viewBox={ format!("0 0 {} {}", INNER.0, INNER.1) } xmlns="http://www.w3.org/2000/svg">
-                            <Chart color="darkgreen" data={self.pressure_data.clone()} time_span={self.time_span.clone()} elapsed={self.elapsed()} data_codomain={(565.0, 305.0)} />
-                            <Chart color="darkred" data={self.temp_basket_data.clone()} time_span={self.time_span.clone()} elapsed={self.elapsed()} data_codomain={(645.0, 585.0)} />
-                            <Chart color="red" data={self.temp_mix_data.clone()} time_span={self.time_span.clone()} elapsed={self.elapsed()} data_codomain={(645.0, 585.0)} />
-                            <Chart color="#1f77ea" data={self.flow_data.clone()} time_span={self.time_span.clone()} elapsed={self.elapsed()} data_codomain={(565.0, 465.0)} />
-                            <Chart color="#8f6400" data={self.flow_by_weight_data.clone()} time_span={self.time_span.clone()} elapsed={self.elapsed()} data_codomain={(565.0, 465.0)} />
-                        </svg>
+                        { self.render_overlay(ctx, overlay.clone()) }
                     </div>
                 </div>
+                <ExportFrame {overlay} video={self.video.clone()} t={self.t()} />
+                <ExportAnimation
+                    charts={self.charts.clone()}
+                    units={self.units}
+                    time_span={self.time_span.clone()}
+                    settings={ctx.props().settings.clone()}
+                    size={self.frame.size()}
+                    video={self.video.clone()}
+                />
+                <ExportSubtitles
+                    data={self.data.clone()}
+                    charts={self.charts.clone()}
+                    units={self.units}
+                    settings={ctx.props().settings.clone()}
+                    size={self.frame.size()}
+                />
+                <ExportCsv data={self.data.clone()} />
+                <FfmpegCommand
+                    time_span={self.time_span.clone()}
+                    settings={ctx.props().settings.clone()}
+                    video_size={self.video_size()}
+                />
             </>
         }
     }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            self.observe_resize(ctx);
+        }
+    }
+
+    fn destroy(&mut self, _: &Context<Self>) {
+        if let Some((observer, _)) = self.observer.take() {
+            observer.disconnect();
+        }
+    }
 }
@@ -1,7 +1,35 @@
+mod animation;
 mod base;
+mod canvas;
 mod chart;
+mod column_mapping;
+mod csv;
+mod diagnostics;
+mod export;
+mod extraction;
+mod ffmpeg;
 mod graph;
+mod overlay_svg;
+mod project;
+mod reference;
+mod settings;
+mod stats;
+mod subtitles;
 
+pub use animation::ExportAnimation;
 pub use base::*;
+pub use canvas::CanvasChart;
 pub use chart::Chart;
-pub use graph::{Graph, INNER};
+pub use column_mapping::ColumnMapping;
+pub use csv::ExportCsv;
+pub use diagnostics::Diagnostics;
+pub use export::ExportFrame;
+pub use extraction::ExtractionPanel;
+pub use ffmpeg::FfmpegCommand;
+pub use graph::Graph;
+pub use overlay_svg::OverlaySvg;
+pub use project::ProjectControls;
+pub use reference::ReferenceControls;
+pub use settings::SettingsPanel;
+pub use stats::ShotSummary;
+pub use subtitles::ExportSubtitles;
@@ -0,0 +1,59 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{Overlay, AXIS_LABEL_SIZE, HUD_FONT_SIZE, TICK_LENGTH};
+
+use crate::components::Chart;
+use crate::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub overlay: Rc<Overlay>,
+}
+
+/// Draws an [`Overlay`] as inline SVG.
+pub struct OverlaySvg;
+
+impl Component for OverlaySvg {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let overlay = &ctx.props().overlay;
+        let (width, height) = overlay.size;
+        let theme = &overlay.theme;
+        let axis = &overlay.axis;
+
+        html! {
+            <svg width={ format!("{}", width) } height={ format!("{}", height) } viewBox={ format!("0 0 {} {}", width, height) } xmlns="http://www.w3.org/2000/svg">
+                <g opacity={ format!("{}", overlay.opacity) }>
+                    if let Some(panel) = &overlay.panel {
+                        <rect x={ format!("{}", panel.rect.x) } y={ format!("{}", panel.rect.y) } width={ format!("{}", panel.rect.width) } height={ format!("{}", panel.rect.height) } rx={ format!("{}", panel.radius) } fill={ panel.fill } />
+                    }
+                    <g stroke={ theme.axis } stroke-width="1px">
+                        <line x1={ format!("{}", axis.from.0) } y1={ format!("{}", axis.from.1) } x2={ format!("{}", axis.to.0) } y2={ format!("{}", axis.to.1) } />
+                        { for axis.ticks.iter().map(|tick| html! {
+                            <line x1={ format!("{}", tick.x) } y1={ format!("{}", axis.from.1) } x2={ format!("{}", tick.x) } y2={ format!("{}", axis.from.1 + TICK_LENGTH) } />
+                        }) }
+                    </g>
+                    <g fill={ theme.text } font-size={ format!("{}px", AXIS_LABEL_SIZE) } text-anchor="middle" font-family="sans-serif">
+                        { for axis.ticks.iter().map(|tick| html! {
+                            <text x={ format!("{}", tick.x) } y={ format!("{}", axis.from.1 + TICK_LENGTH + AXIS_LABEL_SIZE) }>{ &tick.label }</text>
+                        }) }
+                    </g>
+                    <g font-size={ format!("{}px", HUD_FONT_SIZE) } font-family="sans-serif">
+                        { for overlay.hud.lines.iter().map(|line| html! {
+                            <text x={ format!("{}", line.position.0) } y={ format!("{}", line.position.1) } fill={ theme.color(line.kind) }>{ &line.text }</text>
+                        }) }
+                    </g>
+                    { for overlay.strokes.iter().map(|stroke| html! {
+                        <Chart stroke={stroke.clone()} />
+                    }) }
+                </g>
+            </svg>
+        }
+    }
+}
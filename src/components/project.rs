@@ -0,0 +1,58 @@
+use decent_overlay_core::PROJECT_EXTENSION;
+use gloo::file::File;
+use yew::{Callback, Event, MouseEvent};
+
+use crate::prelude::*;
+use crate::web::picked_file;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub onsave: Callback<MouseEvent>,
+    /// Downloads the shot file, when the shot has one.
+    #[prop_or_default]
+    pub ondownload_shot: Option<Callback<MouseEvent>>,
+    pub onopen_project: Callback<File>,
+    pub onopen_video: Callback<File>,
+    /// Outcome of the last open, e.g. a parse error or a video mismatch.
+    pub status: Option<String>,
+}
+
+/// Saves and opens `.dvo.json` project files, and picks the video to sync against.
+#[function_component(ProjectControls)]
+pub fn project_controls(props: &Props) -> Html {
+    let onchange = |callback: &Callback<File>| {
+        let callback = callback.clone();
+        Callback::from(move |e: Event| {
+            if let Some(file) = picked_file(&e) {
+                callback.emit(file);
+            }
+        })
+    };
+
+    html! {
+        <div class={css!(r#"
+            display: flex;
+            flex-wrap: wrap;
+            gap: 8px;
+            align-items: center;
+            margin-bottom: 8px;
+            font-size: 14px;
+        "#)}>
+            <button onclick={props.onsave.clone()}>{ "Save project" }</button>
+            if let Some(ondownload_shot) = &props.ondownload_shot {
+                <button onclick={ondownload_shot.clone()}>{ "Download shot" }</button>
+            }
+            <label>
+                { "Open project " }
+                <input type="file" accept={ format!("{},.json", PROJECT_EXTENSION) } onchange={onchange(&props.onopen_project)} />
+            </label>
+            <label>
+                { "Open video " }
+                <input type="file" accept="video/*" onchange={onchange(&props.onopen_video)} />
+            </label>
+            if let Some(status) = &props.status {
+                <span>{ status }</span>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,81 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{models::ShotData, Alignment};
+use gloo::file::File;
+use web_sys::HtmlSelectElement;
+use yew::{AttrValue, Callback, Event, TargetCast};
+
+use crate::prelude::*;
+use crate::web::picked_file;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub data: Rc<ShotData>,
+    /// Name of the file of the reference shot, and the shot.
+    pub reference: Option<(AttrValue, Rc<ShotData>)>,
+    pub alignment: Alignment,
+    pub onopen: Callback<File>,
+    pub onalign: Callback<Alignment>,
+    pub onclear: Callback<()>,
+}
+
+/// Picks a shot to draw under the current one, e.g. yesterday's, and how to line them up.
+#[function_component(ReferenceControls)]
+pub fn reference_controls(props: &Props) -> Html {
+    let options = Alignment::options(&props.data);
+    let onchange_file = {
+        let onopen = props.onopen.clone();
+        Callback::from(move |e: Event| {
+            if let Some(file) = picked_file(&e) {
+                onopen.emit(file);
+            }
+        })
+    };
+    let onchange_alignment = {
+        let onalign = props.onalign.clone();
+        let alignments: Vec<_> = options.iter().map(|(alignment, _)| *alignment).collect();
+        Callback::from(move |e: Event| {
+            let index = e
+                .target_unchecked_into::<HtmlSelectElement>()
+                .selected_index() as usize;
+            if let Some(alignment) = alignments.get(index) {
+                onalign.emit(*alignment);
+            }
+        })
+    };
+    let onclear = props.onclear.reform(|_| ());
+
+    html! {
+        <div class={css!(r#"
+            display: flex;
+            flex-wrap: wrap;
+            gap: 8px;
+            align-items: center;
+            margin-bottom: 8px;
+            font-size: 14px;
+        "#)}>
+            <label>
+                { "Compare with " }
+                <input type="file" accept=".json,.shot" onchange={onchange_file} />
+            </label>
+            if let Some((name, reference)) = &props.reference {
+                <span>{ name }</span>
+                <label>
+                    { "Line up " }
+                    <select onchange={onchange_alignment}>
+                        { for options.iter().map(|(alignment, label)| html! {
+                            <option selected={*alignment == props.alignment}>{ label }</option>
+                        }) }
+                    </select>
+                </label>
+                <span>
+                    { match props.alignment.offset(&props.data, reference) {
+                        Some(offset) => format!("reference moved {:+.1} s", offset),
+                        None => "not in both shots, lined up by start".into(),
+                    } }
+                </span>
+                <button onclick={onclear}>{ "Clear" }</button>
+            }
+        </div>
+    }
+}
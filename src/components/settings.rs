@@ -0,0 +1,264 @@
+use decent_overlay_core::{
+    models::SeriesKind, Backend, Placement, PressureUnit, Rect, Settings, TemperatureUnit,
+    ThemePreset, Units, VolumeUnit, WeightUnit,
+};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::{Callback, Event, InputEvent, TargetCast};
+
+use crate::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub settings: Settings,
+    pub onchange: Callback<Settings>,
+}
+
+/// Builds a callback that applies `f` to a copy of the current settings and emits it.
+fn update<E: 'static>(props: &Props, f: impl Fn(&mut Settings, E) + 'static) -> Callback<E> {
+    let settings = props.settings.clone();
+    let onchange = props.onchange.clone();
+    Callback::from(move |e: E| {
+        let mut settings = settings.clone();
+        f(&mut settings, e);
+        onchange.emit(settings);
+    })
+}
+
+fn selected_index(e: &Event) -> usize {
+    e.target_unchecked_into::<HtmlSelectElement>()
+        .selected_index() as usize
+}
+
+fn input_value(e: &InputEvent) -> f32 {
+    e.target_unchecked_into::<HtmlInputElement>()
+        .value_as_number() as f32
+}
+
+fn render_custom_rect(props: &Props, rect: &Rect) -> Html {
+    let field = |label: &'static str, value: f32, set: fn(&mut Rect, f32)| {
+        let oninput = update(props, move |settings, e: InputEvent| {
+            let percent = input_value(&e);
+            if !percent.is_finite() || !(0. ..=100.).contains(&percent) {
+                return;
+            }
+            if let Placement::Custom(rect) = &mut settings.placement {
+                set(rect, percent / 100.);
+            }
+        });
+        html! {
+            <label>
+                { label }
+                <input type="number" min="0" max="100" step="1" value={ format!("{:.0}", value * 100.) } {oninput} />
+            </label>
+        }
+    };
+
+    html! {
+        <>
+            { field("X% ", rect.x, |rect, v| rect.x = v) }
+            { field("Y% ", rect.y, |rect, v| rect.y = v) }
+            { field("W% ", rect.width, |rect, v| rect.width = v) }
+            { field("H% ", rect.height, |rect, v| rect.height = v) }
+        </>
+    }
+}
+
+fn render_series_toggle(props: &Props, kind: SeriesKind) -> Html {
+    let onchange = update(props, move |settings, e: Event| {
+        let checked = e.target_unchecked_into::<HtmlInputElement>().checked();
+        // Rebuild from `ALL` to keep the drawing order stable.
+        settings.series = SeriesKind::ALL
+            .into_iter()
+            .filter(|other| {
+                if *other == kind {
+                    checked
+                } else {
+                    settings.series.contains(other)
+                }
+            })
+            .collect();
+    });
+    html! {
+        <label>
+            <input type="checkbox" checked={props.settings.series.contains(&kind)} {onchange} />
+            { format!(" {}", kind.label()) }
+        </label>
+    }
+}
+
+const UNIT_PRESETS: [(&str, Option<Units>); 3] = [
+    ("From shot", None),
+    ("Metric", Some(Units::METRIC)),
+    ("Imperial", Some(Units::IMPERIAL)),
+];
+
+fn render_unit_select<U: Copy + PartialEq + 'static>(
+    props: &Props,
+    all: &'static [U],
+    current: U,
+    symbol: fn(&U) -> &'static str,
+    set: fn(&mut Units, U),
+) -> Html {
+    let onchange = update(props, move |settings, e: Event| {
+        if let (Some(units), Some(unit)) = (&mut settings.units, all.get(selected_index(&e))) {
+            set(units, *unit);
+        }
+    });
+    html! {
+        <select {onchange}>
+            { for all.iter().map(|unit| html! {
+                <option selected={*unit == current}>{ symbol(unit) }</option>
+            }) }
+        </select>
+    }
+}
+
+fn render_units(props: &Props, units: &Units) -> Html {
+    html! {
+        <>
+            { render_unit_select(props, &TemperatureUnit::ALL, units.temperature, TemperatureUnit::symbol, |units, unit| units.temperature = unit) }
+            { render_unit_select(props, &PressureUnit::ALL, units.pressure, PressureUnit::symbol, |units, unit| units.pressure = unit) }
+            { render_unit_select(props, &VolumeUnit::ALL, units.volume, VolumeUnit::symbol, |units, unit| units.volume = unit) }
+            { render_unit_select(props, &WeightUnit::ALL, units.weight, WeightUnit::symbol, |units, unit| units.weight = unit) }
+        </>
+    }
+}
+
+#[function_component(SettingsPanel)]
+pub fn settings_panel(props: &Props) -> Html {
+    let settings = &props.settings;
+
+    let onchange_backend = update(props, |settings, e: Event| {
+        if let Some(backend) = Backend::ALL.get(selected_index(&e)) {
+            settings.backend = *backend;
+        }
+    });
+
+    let onchange_placement = update(props, |settings, e: Event| {
+        settings.placement = match Placement::PRESETS.get(selected_index(&e)) {
+            Some(placement) => placement.clone(),
+            None => Placement::Custom(settings.placement.rect()),
+        };
+    });
+
+    let onchange_theme = update(props, |settings, e: Event| {
+        if let Some(theme) = ThemePreset::ALL.get(selected_index(&e)) {
+            settings.theme = *theme;
+        }
+    });
+
+    let onchange_units = update(props, |settings, e: Event| {
+        settings.units = match UNIT_PRESETS.get(selected_index(&e)) {
+            Some((_, units)) => *units,
+            None => Some(settings.units.unwrap_or_default()),
+        };
+    });
+
+    let oninput_opacity = update(props, |settings, e: InputEvent| {
+        settings.opacity = input_value(&e);
+    });
+
+    let oninput_offset = update(props, |settings, e: InputEvent| {
+        let offset = input_value(&e);
+        if offset.is_finite() {
+            settings.offset = offset;
+        }
+    });
+
+    let oninput_pre_roll = update(props, |settings, e: InputEvent| {
+        let pre_roll = input_value(&e);
+        if pre_roll.is_finite() && 0. <= pre_roll {
+            settings.trim.pre_roll = pre_roll;
+        }
+    });
+
+    let oninput_post_roll = update(props, |settings, e: InputEvent| {
+        let post_roll = input_value(&e);
+        if post_roll.is_finite() && 0. <= post_roll {
+            settings.trim.post_roll = post_roll;
+        }
+    });
+
+    let onchange_panel = update(props, |settings, e: Event| {
+        settings.panel = e.target_unchecked_into::<HtmlInputElement>().checked();
+    });
+
+    html! {
+        <div class={css!(r#"
+            display: flex;
+            flex-wrap: wrap;
+            gap: 16px;
+            margin-bottom: 8px;
+            font-size: 14px;
+
+            input[type="number"] {
+                width: 48px;
+            }
+        "#)}>
+            <label>
+                { "Renderer " }
+                <select onchange={onchange_backend}>
+                    { for Backend::ALL.iter().map(|backend| html! {
+                        <option selected={*backend == settings.backend}>{ backend.label() }</option>
+                    }) }
+                </select>
+            </label>
+            <label>
+                { "Placement " }
+                <select onchange={onchange_placement}>
+                    { for Placement::PRESETS.iter().map(|placement| html! {
+                        <option selected={*placement == settings.placement}>{ placement.label() }</option>
+                    }) }
+                    <option selected={matches!(settings.placement, Placement::Custom(_))}>{ "Custom" }</option>
+                </select>
+            </label>
+            if let Placement::Custom(rect) = &settings.placement {
+                { render_custom_rect(props, rect) }
+            }
+            <label>
+                { "Theme " }
+                <select onchange={onchange_theme}>
+                    { for ThemePreset::ALL.iter().map(|theme| html! {
+                        <option selected={*theme == settings.theme}>{ theme.label() }</option>
+                    }) }
+                </select>
+            </label>
+            { for SeriesKind::ALL.iter().map(|kind| render_series_toggle(props, *kind)) }
+            <label>
+                { "Units " }
+                <select onchange={onchange_units}>
+                    { for UNIT_PRESETS.iter().map(|(label, units)| html! {
+                        <option selected={*units == settings.units}>{ label }</option>
+                    }) }
+                    <option selected={!UNIT_PRESETS.iter().any(|(_, units)| *units == settings.units)}>{ "Custom" }</option>
+                </select>
+            </label>
+            if let Some(units) = &settings.units {
+                { render_units(props, units) }
+            }
+            <label>
+                { "Opacity " }
+                <input type="range" min="0" max="1" step="0.05" value={ format!("{}", settings.opacity) } oninput={oninput_opacity} />
+            </label>
+            <label>
+                { "Sync offset " }
+                <input type="number" step="0.1" value={ format!("{}", settings.offset) } oninput={oninput_offset} />
+                { " s" }
+            </label>
+            <label>
+                { "Pre-roll " }
+                <input type="number" min="0" step="0.5" value={ format!("{}", settings.trim.pre_roll) } oninput={oninput_pre_roll} />
+                { " s" }
+            </label>
+            <label>
+                { "Post-roll " }
+                <input type="number" min="0" step="0.5" value={ format!("{}", settings.trim.post_roll) } oninput={oninput_post_roll} />
+                { " s" }
+            </label>
+            <label>
+                <input type="checkbox" checked={settings.panel} onchange={onchange_panel} />
+                { " Background panel" }
+            </label>
+        </div>
+    }
+}
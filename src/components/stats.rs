@@ -0,0 +1,121 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{
+    models::ShotData,
+    stats::{PhaseStats, ShotStats, Summary},
+    Quantity, Units,
+};
+
+use crate::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub data: Rc<ShotData>,
+    pub units: Units,
+}
+
+fn or_dash(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "-".into())
+}
+
+fn seconds(value: Option<f32>) -> String {
+    or_dash(value.map(|t| format!("{:.1} s", t)))
+}
+
+fn render_row(label: &str, value: String) -> Html {
+    html! {
+        <tr>
+            <th>{ label }</th>
+            <td>{ value }</td>
+        </tr>
+    }
+}
+
+fn render_phase(phase: &PhaseStats, index: usize, units: &Units) -> Html {
+    let summary = |quantity: Quantity, summary: Option<Summary>| {
+        or_dash(summary.map(|summary| {
+            format!(
+                "{:.1} / {:.1} {}",
+                units.convert(quantity, summary.peak),
+                units.convert(quantity, summary.mean),
+                units.symbol(quantity)
+            )
+        }))
+    };
+    html! {
+        <tr>
+            <th>{ phase.name.clone().unwrap_or_else(|| format!("{}", index + 1)) }</th>
+            <td>{ format!("{:.1} s", phase.start) }</td>
+            <td>{ format!("{:.1} s", phase.duration) }</td>
+            <td>{ summary(Quantity::Pressure, phase.pressure) }</td>
+            <td>{ summary(Quantity::Flow, phase.flow) }</td>
+        </tr>
+    }
+}
+
+/// Times, ratios and per-step peaks computed by [`ShotStats`].
+#[function_component(ShotSummary)]
+pub fn shot_summary(props: &Props) -> Html {
+    let stats = ShotStats::of(&props.data);
+    let units = &props.units;
+    let value = |quantity: Quantity, value: Option<f32>| {
+        or_dash(value.map(|value| {
+            format!(
+                "{:.1} {}",
+                units.convert(quantity, value),
+                units.symbol(quantity)
+            )
+        }))
+    };
+    let volume = |ml: Option<f32>| {
+        or_dash(ml.map(|ml| format!("{:.1} {}", units.volume.convert(ml), units.volume.symbol())))
+    };
+    // A difference of temperatures, so without the offset of the scale.
+    let deviation = stats.temperature_deviation.map(|celsius| {
+        units.convert(Quantity::Temperature, celsius) - units.convert(Quantity::Temperature, 0.)
+    });
+
+    html! {
+        <div class={css!(r#"
+            display: flex;
+            flex-wrap: wrap;
+            gap: 32px;
+            margin: 8px 0;
+            font-size: 14px;
+
+            th {
+                padding-right: 16px;
+                font-weight: 400;
+                text-align: left;
+                color: #606060;
+            }
+
+            td {
+                padding-right: 16px;
+            }
+        "#)}>
+            <table>
+                { render_row("Duration", format!("{:.1} s", stats.total_time)) }
+                { render_row("Preinfusion", seconds(stats.preinfusion_time)) }
+                { render_row("First drops", seconds(stats.first_drops_time)) }
+                { render_row("Final weight", value(Quantity::Weight, stats.final_weight)) }
+                { render_row("Brew ratio", or_dash(stats.brew_ratio.map(|ratio| format!("1:{:.2}", ratio)))) }
+            </table>
+            <table>
+                { render_row("Temperature off goal", or_dash(deviation.map(|d| format!("{:.1} {}", d, units.symbol(Quantity::Temperature))))) }
+                { render_row("Water dispensed", volume(stats.water_dispensed)) }
+                { render_row("Puck retention", volume(stats.puck_retention)) }
+            </table>
+            <table>
+                <tr>
+                    <th>{ "Step" }</th>
+                    <th>{ "Start" }</th>
+                    <th>{ "Time" }</th>
+                    <th>{ "Pressure peak / mean" }</th>
+                    <th>{ "Flow peak / mean" }</th>
+                </tr>
+                { for stats.phases.iter().enumerate().map(|(i, phase)| render_phase(phase, i, units)) }
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,93 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{
+    cues,
+    models::{ChartData, SeriesKind, ShotData},
+    to_ass, Range, Settings, SubtitleFormat, Units,
+};
+use web_sys::HtmlInputElement;
+use yew::{use_state, Callback, Event, TargetCast};
+
+use crate::prelude::*;
+use crate::web::download_bytes;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub data: Rc<ShotData>,
+    pub charts: Rc<Vec<(SeriesKind, Rc<ChartData>)>>,
+    pub units: Units,
+    pub settings: Settings,
+    /// Size of the video frame the overlay is laid out on.
+    pub size: (f32, f32),
+}
+
+#[function_component(ExportSubtitles)]
+pub fn export_subtitles(props: &Props) -> Html {
+    let interval = use_state(|| 1.0f32);
+
+    let onclick = |format: SubtitleFormat| {
+        let data = props.data.clone();
+        let units = props.units;
+        let offset = props.settings.offset;
+        let interval = *interval;
+        Callback::from(move |_| {
+            let text = format.write(&cues(&data, &units, interval, offset));
+            download_bytes(
+                &format!("overlay.{}", format.extension()),
+                format.mime(),
+                text.as_bytes(),
+            );
+        })
+    };
+
+    let onclick_ass = {
+        let data = props.data.clone();
+        let charts = props.charts.clone();
+        let units = props.units;
+        let settings = props.settings.clone();
+        let size = props.size;
+        let interval = *interval;
+        Callback::from(move |_| {
+            let time_span = Range::from_series(&data.elapsed);
+            let ass = to_ass(&charts, &units, &time_span, &settings, size, interval);
+            download_bytes("overlay.ass", "text/x-ssa", ass.as_bytes());
+        })
+    };
+
+    let onchange_interval = {
+        let interval = interval.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<HtmlInputElement>()
+                .value_as_number();
+            if value.is_finite() && 0. < value {
+                interval.set(value as f32);
+            }
+        })
+    };
+
+    html! {
+        <div class={css!(r#"
+            display: flex;
+            gap: 8px;
+            align-items: center;
+            margin-top: 8px;
+            font-size: 14px;
+
+            input[type="number"] {
+                width: 64px;
+            }
+        "#)}>
+            <span>{ "Export subtitles" }</span>
+            <label>
+                { "Every " }
+                <input type="number" min="0.1" step="0.1" value={ format!("{}", *interval) } onchange={onchange_interval} />
+                { " s" }
+            </label>
+            { for SubtitleFormat::ALL.iter().map(|format| html! {
+                <button onclick={onclick(*format)}>{ format.label() }</button>
+            }) }
+            <button onclick={onclick_ass}>{ "ASS" }</button>
+        </div>
+    }
+}
@@ -1,6 +0,0 @@
-pub mod models;
-mod range;
-mod scale;
-
-pub use range::Range;
-pub use scale::scale;
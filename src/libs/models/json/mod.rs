@@ -1,6 +0,0 @@
-mod shot_data;
-
-pub use shot_data::*;
-
-pub const SHOT1: &'static str = include_str!("../../../../data/shot1.json");
-pub const SHOT2: &'static str = include_str!("../../../../data/shot2.json");
@@ -2,10 +2,11 @@ use yew::prelude::*;
 use yew_router::prelude::*;
 
 mod components;
-mod libs;
 mod pages;
 mod prelude;
 mod routes;
+mod shots;
+mod web;
 
 struct App;
 
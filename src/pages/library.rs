@@ -0,0 +1,346 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{
+    models::{json::SAMPLES, ShotData},
+    read_archive, read_csv, sort_shots, CsvTable, LibraryShot, SortKey,
+};
+use gloo::file::File;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::{Callback, DragEvent, Event, InputEvent, TargetCast};
+
+use crate::components::{ColumnMapping, Content, Heading};
+use crate::prelude::*;
+use crate::web::{
+    delete_shot, dropped_files, load_shots, picked_files, read_bytes, read_text, save_shots,
+};
+
+pub enum Msg {
+    Loaded(Result<Vec<LibraryShot>, String>),
+    Import(Vec<File>),
+    Imported(Import),
+    /// Columns of the first pending CSV file were mapped.
+    ImportCsv(Box<ShotData>),
+    SkipCsv,
+    CsvImported(Result<(), String>),
+    Delete(String),
+    Deleted(Result<(), String>),
+    Search(String),
+    Sort(SortKey),
+    ToggleOrder,
+}
+
+/// Outcome of importing a batch of files.
+pub struct Import {
+    imported: usize,
+    /// File name and reason of every file that was skipped.
+    failures: Vec<(String, String)>,
+    /// CSV files, left for the user to tell which column holds what.
+    tables: Vec<(String, CsvTable)>,
+}
+
+async fn import(files: Vec<File>) -> Result<Import, String> {
+    let mut shots = vec![];
+    let mut failures = vec![];
+    let mut tables = vec![];
+    for file in files {
+        let name = file.name();
+        if let Some(stem) = name.strip_suffix(".csv").or(name.strip_suffix(".CSV")) {
+            let table = match read_text(&file).await {
+                Ok(text) => read_csv(&text),
+                Err(e) => Err(e.to_string()),
+            };
+            match table {
+                Ok(table) => tables.push((stem.to_string(), table)),
+                Err(e) => failures.push((name, e)),
+            }
+            continue;
+        }
+
+        if file.name().to_lowercase().ends_with(".zip") {
+            let entries = match read_bytes(&file).await {
+                Ok(bytes) => read_archive(&bytes),
+                Err(e) => Err(e.to_string()),
+            };
+            match entries {
+                Ok(entries) => {
+                    for entry in entries {
+                        match entry.shot {
+                            Ok(shot) => shots.push(shot),
+                            Err(e) => {
+                                failures.push((format!("{}: {}", file.name(), entry.name), e))
+                            }
+                        }
+                    }
+                }
+                Err(e) => failures.push((file.name(), e)),
+            }
+            continue;
+        }
+
+        let parsed = match read_text(&file).await {
+            Ok(text) => LibraryShot::parse(&file.name(), &text),
+            Err(e) => Err(e.to_string()),
+        };
+        match parsed {
+            Ok(shot) => shots.push(shot),
+            Err(e) => failures.push((file.name(), e)),
+        }
+    }
+    save_shots(&shots).await?;
+    Ok(Import {
+        imported: shots.len(),
+        failures,
+        tables,
+    })
+}
+
+/// Shots imported into the browser, with search, sort and delete.
+pub struct LibraryPage {
+    shots: Vec<LibraryShot>,
+    query: String,
+    sort: SortKey,
+    descending: bool,
+    status: Option<String>,
+    failures: Vec<(String, String)>,
+    /// CSV files waiting for their columns to be mapped, first one shown,
+    /// each with a number of its own as file names may repeat.
+    pending: Vec<(usize, String, Rc<CsvTable>)>,
+    /// Number of the next CSV file to be left pending.
+    next_pending: usize,
+}
+
+impl LibraryPage {
+    fn reload(ctx: &Context<Self>) {
+        ctx.link()
+            .send_future(async { Msg::Loaded(load_shots().await) });
+    }
+
+    fn render_row(&self, ctx: &Context<Self>, shot: &LibraryShot) -> Html {
+        let meta = &shot.data.meta;
+        let id = shot.id.clone();
+        html! {
+            <tr key={shot.id.clone()}>
+                <td>{ &shot.data.date }</td>
+                <td>{ &shot.data.profile.title }</td>
+                <td>{ shot.bean() }</td>
+                <td>{ format!("{:.1} / {:.1} g", meta.dose, meta.beverage) }</td>
+                <td>{ format!("{:.1} s", shot.duration()) }</td>
+                <td>
+                    <Link to={Route::Player { id: shot.id.clone() }}>{ "Open in player" }</Link>
+                    <Link to={Route::Shot { id: shot.id.clone() }}>{ "Details" }</Link>
+                    <button onclick={ctx.link().callback(move |_| Msg::Delete(id.clone()))}>{ "Delete" }</button>
+                </td>
+            </tr>
+        }
+    }
+}
+
+impl Component for LibraryPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self::reload(ctx);
+        Self {
+            shots: vec![],
+            query: String::default(),
+            sort: SortKey::default(),
+            descending: true,
+            status: None,
+            failures: vec![],
+            pending: vec![],
+            next_pending: 0,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Loaded(Ok(shots)) => {
+                self.shots = shots;
+                sort_shots(&mut self.shots, self.sort, self.descending);
+                true
+            }
+            Msg::Loaded(Err(e)) | Msg::Deleted(Err(e)) => {
+                self.status = Some(e);
+                true
+            }
+            Msg::Import(files) => {
+                self.status = Some(format!("Importing {} files…", files.len()));
+                ctx.link().send_future(async move {
+                    match import(files).await {
+                        Ok(import) => Msg::Imported(import),
+                        Err(e) => Msg::Loaded(Err(e)),
+                    }
+                });
+                true
+            }
+            Msg::Imported(import) => {
+                self.status = Some(format!(
+                    "Imported {} shots, skipped {}",
+                    import.imported,
+                    import.failures.len()
+                ));
+                self.failures = import.failures;
+                for (name, table) in import.tables {
+                    self.pending.push((self.next_pending, name, Rc::new(table)));
+                    self.next_pending += 1;
+                }
+                Self::reload(ctx);
+                true
+            }
+            Msg::ImportCsv(data) => {
+                if self.pending.is_empty() {
+                    return false;
+                }
+                let (_, name, _) = self.pending.remove(0);
+                let shot = LibraryShot::new(&format!("{}.csv", name), *data);
+                ctx.link()
+                    .send_future(async move { Msg::CsvImported(save_shots(&[shot]).await) });
+                true
+            }
+            Msg::SkipCsv => {
+                if !self.pending.is_empty() {
+                    self.pending.remove(0);
+                }
+                true
+            }
+            Msg::CsvImported(result) => {
+                self.status = Some(match result {
+                    Ok(()) => "Imported 1 shot from CSV".into(),
+                    Err(e) => e,
+                });
+                Self::reload(ctx);
+                true
+            }
+            Msg::Delete(id) => {
+                ctx.link()
+                    .send_future(async move { Msg::Deleted(delete_shot(&id).await) });
+                false
+            }
+            Msg::Deleted(Ok(())) => {
+                Self::reload(ctx);
+                false
+            }
+            Msg::Search(query) => {
+                self.query = query;
+                true
+            }
+            Msg::Sort(sort) => {
+                self.sort = sort;
+                sort_shots(&mut self.shots, self.sort, self.descending);
+                true
+            }
+            Msg::ToggleOrder => {
+                self.descending = !self.descending;
+                sort_shots(&mut self.shots, self.sort, self.descending);
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let onchange_files = ctx
+            .link()
+            .callback(|e: Event| Msg::Import(picked_files(&e)));
+        let ondrop = ctx
+            .link()
+            .callback(|e: DragEvent| Msg::Import(dropped_files(&e)));
+        let ondragover = Callback::from(|e: DragEvent| e.prevent_default());
+        let oninput_search = ctx.link().callback(|e: InputEvent| {
+            Msg::Search(e.target_unchecked_into::<HtmlInputElement>().value())
+        });
+        let onchange_sort = ctx.link().batch_callback(|e: Event| {
+            let index = e
+                .target_unchecked_into::<HtmlSelectElement>()
+                .selected_index() as usize;
+            SortKey::ALL.get(index).copied().map(Msg::Sort)
+        });
+
+        html! {
+            <Page title="Library">
+                <Heading>{ "Library" }</Heading>
+                <Content>
+                    <div {ondrop} {ondragover} class={css!(r#"
+                        display: flex;
+                        flex-wrap: wrap;
+                        gap: 8px;
+                        align-items: center;
+                        margin-bottom: 8px;
+                        padding: 8px;
+                        border: 1px dashed #c0c0c0;
+                        font-size: 14px;
+                    "#)}>
+                        <label>
+                            { "Import shots, CSV files or a zipped history folder " }
+                            <input type="file" accept=".json,.shot,.csv,.zip" multiple=true onchange={onchange_files} />
+                        </label>
+                        <input type="search" placeholder="Search" value={self.query.clone()} oninput={oninput_search} />
+                        <label>
+                            { "Sort by " }
+                            <select onchange={onchange_sort}>
+                                { for SortKey::ALL.iter().map(|sort| html! {
+                                    <option selected={*sort == self.sort}>{ sort.label() }</option>
+                                }) }
+                            </select>
+                        </label>
+                        <button onclick={ctx.link().callback(|_| Msg::ToggleOrder)}>
+                            { if self.descending { "Descending" } else { "Ascending" } }
+                        </button>
+                        if let Some(status) = &self.status {
+                            <span>{ status }</span>
+                        }
+                    </div>
+                    if let Some((key, name, table)) = self.pending.first() {
+                        <ColumnMapping
+                            key={*key}
+                            name={name.clone()}
+                            table={table.clone()}
+                            onimport={ctx.link().callback(|data| Msg::ImportCsv(Box::new(data)))}
+                            oncancel={ctx.link().callback(|_| Msg::SkipCsv)}
+                        />
+                    }
+                    if !self.failures.is_empty() {
+                        <ul>
+                            { for self.failures.iter().map(|(name, e)| html! {
+                                <li>{ format!("{}: {}", name, e) }</li>
+                            }) }
+                        </ul>
+                    }
+                    <table class={css!(r#"
+                        width: 100%;
+                        font-size: 14px;
+                        border-collapse: collapse;
+
+                        th {
+                            font-weight: 400;
+                            text-align: left;
+                            color: #606060;
+                        }
+
+                        td a {
+                            margin-right: 8px;
+                        }
+                    "#)}>
+                        <tr>
+                            <th>{ "Date" }</th>
+                            <th>{ "Profile" }</th>
+                            <th>{ "Bean" }</th>
+                            <th>{ "Dose / yield" }</th>
+                            <th>{ "Duration" }</th>
+                            <th />
+                        </tr>
+                        { for self.shots.iter().filter(|shot| shot.matches(&self.query)).map(|shot| self.render_row(ctx, shot)) }
+                    </table>
+                    <h2>{ "Samples" }</h2>
+                    <ul>
+                        { for SAMPLES.iter().map(|(id, _)| html! {
+                            <li>
+                                <Link to={Route::Player { id: id.to_string() }}>{ *id }</Link>
+                            </li>
+                        }) }
+                    </ul>
+                </Content>
+            </Page>
+        }
+    }
+}
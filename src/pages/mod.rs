@@ -1,7 +1,11 @@
 mod about;
 mod errors;
+mod library;
 mod player;
+mod shot;
 
 pub use about::AboutPage;
 pub use errors::NotFoundPage;
+pub use library::LibraryPage;
 pub use player::PlayerPage;
+pub use shot::ShotPage;
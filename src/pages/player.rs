@@ -1,22 +1,363 @@
-use crate::components::{Chart, Content, Graph, Heading};
+use std::rc::Rc;
+
+use decent_overlay_core::{
+    models::ShotData, Alignment, Annotation, Extraction, LibraryShot, Project, Settings, ShotRef,
+    Units, VideoRef, ViewState, PROJECT_EXTENSION,
+};
+use gloo::file::{File, ObjectUrl};
+use gloo::timers::callback::Timeout;
+use yew::AttrValue;
+use yew_router::scope_ext::RouterScopeExt;
+
+use crate::components::{
+    Content, Diagnostics, ExtractionPanel, Graph, Heading, ProjectControls, ReferenceControls,
+    SettingsPanel, ShotSummary,
+};
+use crate::pages::NotFoundPage;
 use crate::prelude::*;
+use crate::shots::{self, Lookup, Shot};
+use crate::web::{download_bytes, read_text, video_ref};
+
+/// Played until a video is opened.
+const DEFAULT_VIDEO: &str = "http://localhost:8888/shot2.mp4";
+/// Quiet period before the link in the address bar is brought up to date.
+const URL_SYNC_DELAY: u32 = 500;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub id: String,
+}
+
+pub enum Msg {
+    ShotLoaded(Lookup),
+    UpdateSettings(Settings),
+    Seek(f32),
+    SyncUrl,
+    SaveProject,
+    DownloadShot,
+    UpdateExtraction(Extraction),
+    OpenProject(File),
+    ProjectOpened(Result<Project, String>),
+    OpenVideo(File),
+    VideoOpened(ObjectUrl, Result<VideoRef, String>),
+    OpenReference(File),
+    ReferenceOpened(Result<Box<LibraryShot>, String>),
+    AlignReference(Alignment),
+    ClearReference,
+}
+
+pub struct PlayerPage {
+    settings: Settings,
+    shot: Lookup,
+    video: Option<(ObjectUrl, VideoRef)>,
+    /// Video the opened project was synced against.
+    expected_video: Option<VideoRef>,
+    annotations: Vec<Annotation>,
+    /// Shot drawn under this one for comparison, with the name of its file.
+    reference: Option<(AttrValue, Rc<ShotData>)>,
+    alignment: Alignment,
+    status: Option<String>,
+    /// Position the graph opened at.
+    start: f32,
+    /// Position last reported by the graph.
+    t: f32,
+    sync: Option<Timeout>,
+    /// Whether the shot is the one of the route, so that a link can show this
+    /// view. A shot opened from a project is not.
+    shareable: bool,
+}
+
+impl PlayerPage {
+    /// Updates the link once the view has stopped changing for a moment.
+    fn schedule_sync(&mut self, ctx: &Context<Self>) {
+        if !self.shareable {
+            return;
+        }
+        let link = ctx.link().clone();
+        self.sync = Some(Timeout::new(URL_SYNC_DELAY, move || {
+            link.send_message(Msg::SyncUrl)
+        }));
+    }
+
+    fn load_shot(&mut self, ctx: &Context<Self>) {
+        self.shot = Lookup::Loading;
+        let id = ctx.props().id.clone();
+        ctx.link()
+            .send_future(async move { Msg::ShotLoaded(shots::find(id).await) });
+    }
+
+    fn project(&self, shot: &Shot) -> Project {
+        let mut project = Project::new(shot.to_ref(), self.settings.clone());
+        project.video = match &self.video {
+            Some((_, video)) => Some(video.clone()),
+            None => self.expected_video.clone(),
+        };
+        project.annotations = self.annotations.clone();
+        project
+    }
+
+    /// Tells whether the open video is the one the project expects.
+    fn video_status(&self) -> Option<String> {
+        let expected = self.expected_video.as_ref()?;
+        match &self.video {
+            None => Some(format!("Open {} to continue", expected.name)),
+            Some((_, video)) if video.size != expected.size || video.hash != expected.hash => {
+                Some(format!(
+                    "{} doesn't match the project's video {}",
+                    video.name, expected.name
+                ))
+            }
+            Some(_) => None,
+        }
+    }
+}
+
+async fn open_reference(file: File) -> Result<Box<LibraryShot>, String> {
+    let text = read_text(&file).await.map_err(|e| e.to_string())?;
+    LibraryShot::parse(&file.name(), &text).map(Box::new)
+}
 
-pub struct PlayerPage;
+async fn open_project(file: File) -> Result<Project, String> {
+    let json = read_text(&file).await.map_err(|e| e.to_string())?;
+    Project::from_json(&json).map_err(|e| e.to_string())
+}
 
 impl Component for PlayerPage {
-    type Message = ();
-    type Properties = ();
+    type Message = Msg;
+    type Properties = Props;
 
-    fn create(_: &Context<Self>) -> Self {
-        Self
+    fn create(ctx: &Context<Self>) -> Self {
+        let view = ctx
+            .link()
+            .location()
+            .and_then(|location| location.query::<Vec<(String, String)>>().ok())
+            .map(ViewState::from_pairs)
+            .unwrap_or_default();
+        let mut settings = Settings::default();
+        view.apply(&mut settings);
+        let start = view.t.unwrap_or_default();
+
+        let mut page = Self {
+            settings,
+            shot: Lookup::Loading,
+            video: None,
+            expected_video: None,
+            annotations: vec![],
+            reference: None,
+            alignment: Alignment::default(),
+            status: None,
+            start,
+            t: start,
+            sync: None,
+            shareable: true,
+        };
+        page.load_shot(ctx);
+        page
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().id != old_props.id {
+            self.load_shot(ctx);
+            self.shareable = true;
+            self.annotations.clear();
+            self.start = 0.;
+            self.t = 0.;
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ShotLoaded(shot) => {
+                self.shot = shot;
+                true
+            }
+            Msg::UpdateSettings(settings) => {
+                self.settings = settings;
+                self.schedule_sync(ctx);
+                true
+            }
+            Msg::Seek(t) => {
+                self.t = t;
+                self.schedule_sync(ctx);
+                false
+            }
+            Msg::SyncUrl => {
+                self.sync = None;
+                let pairs = ViewState::new(&self.settings, self.t).to_pairs();
+                if let Some(navigator) = ctx.link().navigator() {
+                    if let Err(e) = navigator.replace_with_query(
+                        &Route::Player {
+                            id: ctx.props().id.clone(),
+                        },
+                        &pairs,
+                    ) {
+                        log::error!("Failed to update the link: {:?}", e);
+                    }
+                }
+                false
+            }
+            Msg::SaveProject => {
+                let Lookup::Found(shot) = &self.shot else {
+                    return false;
+                };
+                let json = self.project(shot).to_json();
+                download_bytes(
+                    &format!("project{}", PROJECT_EXTENSION),
+                    "application/json",
+                    json.as_bytes(),
+                );
+                false
+            }
+            Msg::DownloadShot => {
+                let Lookup::Found(Shot { raw: Some(raw), .. }) = &self.shot else {
+                    return false;
+                };
+                let json =
+                    serde_json::to_string_pretty(&**raw).expect("shot is always serialisable");
+                download_bytes("shot.json", "application/json", json.as_bytes());
+                false
+            }
+            Msg::UpdateExtraction(extraction) => {
+                let Lookup::Found(shot) = &self.shot else {
+                    return false;
+                };
+                match shot.with_extraction(&extraction) {
+                    Ok(shot) => self.shot = Lookup::Found(shot),
+                    Err(e) => self.status = Some(e),
+                }
+                true
+            }
+            Msg::OpenProject(file) => {
+                ctx.link()
+                    .send_future(async move { Msg::ProjectOpened(open_project(file).await) });
+                false
+            }
+            Msg::ProjectOpened(Err(e)) => {
+                self.status = Some(e);
+                true
+            }
+            Msg::ProjectOpened(Ok(project)) => {
+                let shot = match &project.shot {
+                    ShotRef::Embedded(raw) => Shot::from_value(raw.clone()),
+                    ShotRef::Parsed(data) => Ok(Shot::from((**data).clone())),
+                    ShotRef::Path(_) => {
+                        self.status =
+                            Some("The shot is referenced by path and can't be opened here".into());
+                        return true;
+                    }
+                };
+                match shot {
+                    Ok(shot) => self.shot = Lookup::Found(shot),
+                    Err(e) => {
+                        self.status = Some(format!("invalid shot: {}", e));
+                        return true;
+                    }
+                }
+                self.settings = project.settings;
+                self.annotations = project.annotations;
+                self.expected_video = project.video;
+                self.status = self.video_status();
+                self.t = 0.;
+                // The link would open the shot of the route, not this one.
+                self.shareable = false;
+                self.sync = None;
+                true
+            }
+            Msg::OpenVideo(file) => {
+                let url = ObjectUrl::from(file.clone());
+                ctx.link().send_future(async move {
+                    Msg::VideoOpened(url, video_ref(&file).await.map_err(|e| e.to_string()))
+                });
+                false
+            }
+            Msg::VideoOpened(_, Err(e)) => {
+                self.status = Some(e);
+                true
+            }
+            Msg::VideoOpened(url, Ok(video)) => {
+                self.video = Some((url, video));
+                self.status = self.video_status();
+                true
+            }
+            Msg::OpenReference(file) => {
+                ctx.link()
+                    .send_future(async move { Msg::ReferenceOpened(open_reference(file).await) });
+                false
+            }
+            Msg::ReferenceOpened(Err(e)) => {
+                self.status = Some(e);
+                true
+            }
+            Msg::ReferenceOpened(Ok(shot)) => {
+                let LibraryShot {
+                    file_name, data, ..
+                } = *shot;
+                self.reference = Some((file_name.into(), Rc::new(data)));
+                true
+            }
+            Msg::AlignReference(alignment) => {
+                self.alignment = alignment;
+                true
+            }
+            Msg::ClearReference => {
+                self.reference = None;
+                true
+            }
+        }
     }
 
-    fn view(&self, _: &Context<Self>) -> Html {
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let shot = match &self.shot {
+            Lookup::Loading => return html! { <Page title="Player" /> },
+            Lookup::Missing => return html! { <NotFoundPage /> },
+            Lookup::Found(shot) => shot,
+        };
+        let video_src = match &self.video {
+            Some((url, _)) => AttrValue::from(url.to_string()),
+            None => AttrValue::Static(DEFAULT_VIDEO),
+        };
+        let units = self
+            .settings
+            .units
+            .unwrap_or_else(|| Units::from_shot(&shot.data));
         html! {
             <Page title="Player">
                 <Heading>{ "Player" }</Heading>
                 <Content>
-                    <Graph />
+                    <ProjectControls
+                        onsave={ctx.link().callback(|_| Msg::SaveProject)}
+                        ondownload_shot={shot.raw.is_some().then(|| ctx.link().callback(|_| Msg::DownloadShot))}
+                        onopen_project={ctx.link().callback(Msg::OpenProject)}
+                        onopen_video={ctx.link().callback(Msg::OpenVideo)}
+                        status={self.status.clone().or_else(|| (!self.shareable).then(|| {
+                            "Opened from a project: the link doesn't share this view".to_string()
+                        }))}
+                    />
+                    <ReferenceControls
+                        data={shot.data.clone()}
+                        reference={self.reference.clone()}
+                        alignment={self.alignment}
+                        onopen={ctx.link().callback(Msg::OpenReference)}
+                        onalign={ctx.link().callback(Msg::AlignReference)}
+                        onclear={ctx.link().callback(|_| Msg::ClearReference)}
+                    />
+                    <SettingsPanel settings={self.settings.clone()} onchange={ctx.link().callback(Msg::UpdateSettings)} />
+                    <Graph
+                        data={shot.data.clone()}
+                        {video_src}
+                        settings={self.settings.clone()}
+                        start={self.start}
+                        ontime={ctx.link().callback(Msg::Seek)}
+                        reference={self.reference.as_ref().map(|(_, reference)| reference.clone())}
+                        alignment={self.alignment}
+                    />
+                    <ShotSummary data={shot.data.clone()} {units} />
+                    <ExtractionPanel
+                        extraction={Extraction::of(&shot.data)}
+                        onchange={ctx.link().callback(Msg::UpdateExtraction)}
+                    />
+                    <Diagnostics data={shot.data.clone()} />
                 </Content>
             </Page>
         }
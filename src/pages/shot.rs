@@ -0,0 +1,125 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{
+    models::{ChartData, ShotData},
+    Overlay, Placement, Range, Settings, Units,
+};
+use yew::platform::spawn_local;
+use yew::{use_effect_with_deps, use_state};
+
+use crate::components::{Content, Diagnostics, ExportCsv, Heading, OverlaySvg, ShotSummary};
+use crate::pages::NotFoundPage;
+use crate::prelude::*;
+use crate::shots::{self, Lookup};
+
+/// Size of the full-shot graph.
+const GRAPH_SIZE: (f32, f32) = (640.0, 320.0);
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub id: String,
+}
+
+/// The whole shot drawn at its last sample.
+fn full_graph(data: &ShotData, units: &Units) -> Overlay {
+    let charts = ChartData::all_in(data, units);
+    let time_span = Range::from_series(&data.elapsed);
+    let settings = Settings {
+        placement: Placement::FullWidth,
+        ..Default::default()
+    };
+    Overlay::build(
+        &charts,
+        units,
+        &time_span,
+        time_span.max,
+        GRAPH_SIZE,
+        &settings,
+    )
+}
+
+fn render_row(label: &str, value: String) -> Html {
+    html! {
+        <tr>
+            <th>{ label }</th>
+            <td>{ value }</td>
+        </tr>
+    }
+}
+
+fn render_meta(data: &ShotData) -> Html {
+    let meta = &data.meta;
+    let grams = |value: f32| {
+        if 0. < value {
+            format!("{:.1} g", value)
+        } else {
+            "-".into()
+        }
+    };
+    html! {
+        <table>
+            { render_row("Date", data.date.clone()) }
+            { render_row("Profile", data.profile.title.clone()) }
+            { render_row("Bean", format!("{} {}", meta.bean_brand, meta.bean_type)) }
+            { render_row("Grinder", format!("{} {}", meta.grinder_model, meta.grinder_setting)) }
+            { render_row("Dose", grams(meta.dose)) }
+            { render_row("Yield", grams(meta.beverage)) }
+        </table>
+    }
+}
+
+/// Metadata, statistics and the full graph of one shot.
+#[function_component(ShotPage)]
+pub fn shot_page(props: &Props) -> Html {
+    let lookup = use_state(|| Lookup::Loading);
+    {
+        let lookup = lookup.clone();
+        use_effect_with_deps(
+            move |id: &String| {
+                let id = id.clone();
+                lookup.set(Lookup::Loading);
+                spawn_local(async move { lookup.set(shots::find(id).await) });
+            },
+            props.id.clone(),
+        );
+    }
+
+    let shot = match &*lookup {
+        Lookup::Loading => return html! { <Page title="Shot" /> },
+        Lookup::Missing => return html! { <NotFoundPage /> },
+        Lookup::Found(shot) => shot,
+    };
+    let data = &shot.data;
+    let units = Units::from_shot(data);
+    let overlay = Rc::new(full_graph(data, &units));
+
+    html! {
+        <Page title={ format!("Shot {}", props.id) }>
+            <Heading>{ data.profile.title.clone() }</Heading>
+            <Content>
+                <div class={css!(r#"
+                    display: flex;
+                    flex-wrap: wrap;
+                    gap: 32px;
+                    font-size: 14px;
+
+                    th {
+                        padding-right: 16px;
+                        font-weight: 400;
+                        text-align: left;
+                        color: #606060;
+                    }
+                "#)}>
+                    { render_meta(data) }
+                </div>
+                <ShotSummary data={data.clone()} {units} />
+                <OverlaySvg {overlay} />
+                <Diagnostics data={data.clone()} />
+                <ExportCsv data={data.clone()} />
+                <div>
+                    <Link to={Route::Player { id: props.id.clone() }}>{ "Open in player" }</Link>
+                </div>
+            </Content>
+        </Page>
+    }
+}
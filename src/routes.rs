@@ -1,14 +1,18 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
 
-use crate::pages::{AboutPage, NotFoundPage, PlayerPage};
+use crate::pages::{AboutPage, LibraryPage, NotFoundPage, PlayerPage, ShotPage};
 
 #[derive(Clone, Routable, PartialEq)]
 pub enum Route {
     #[at("/")]
     Home,
-    #[at("/player")]
-    Player,
+    #[at("/player/:id")]
+    Player { id: String },
+    #[at("/library")]
+    Library,
+    #[at("/shots/:id")]
+    Shot { id: String },
     #[at("/about")]
     About,
     #[not_found]
@@ -19,7 +23,9 @@ pub enum Route {
 pub fn switch(routes: Route) -> Html {
     match routes {
         Route::Home => html! { <Redirect<Route> to={Route::About} /> },
-        Route::Player => html! { <PlayerPage /> },
+        Route::Player { id } => html! { <PlayerPage {id} /> },
+        Route::Library => html! { <LibraryPage /> },
+        Route::Shot { id } => html! { <ShotPage {id} /> },
         Route::About => html! { <AboutPage /> },
         Route::NotFound => html! { <NotFoundPage /> },
     }
@@ -0,0 +1,94 @@
+use std::rc::Rc;
+
+use decent_overlay_core::{
+    models::{
+        json::{sample, ShotDataJson},
+        ShotData,
+    },
+    Extraction, ShotRef,
+};
+use serde_json::Value;
+
+use crate::web::get_shot;
+
+/// Shot opened from the header.
+pub const DEFAULT_SHOT: &str = "shot2";
+
+/// A parsed shot, with the DE1 JSON it was read from when there is one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shot {
+    pub raw: Option<Rc<Value>>,
+    pub data: Rc<ShotData>,
+}
+
+impl Shot {
+    pub fn from_value(raw: Value) -> Result<Self, serde_json::Error> {
+        let data = serde_json::from_value::<ShotDataJson>(raw.clone())?.into();
+        Ok(Self {
+            raw: Some(Rc::new(raw)),
+            data: Rc::new(data),
+        })
+    }
+
+    /// The shot with the calculator's values in its metadata, and in the DE1
+    /// JSON too so that projects and downloads carry them.
+    pub fn with_extraction(&self, extraction: &Extraction) -> Result<Self, String> {
+        let raw = match &self.raw {
+            Some(raw) => {
+                let mut raw = (**raw).clone();
+                extraction.apply_json(&mut raw)?;
+                Some(Rc::new(raw))
+            }
+            None => None,
+        };
+        let mut data = (*self.data).clone();
+        extraction.apply(&mut data.meta);
+        Ok(Self {
+            raw,
+            data: Rc::new(data),
+        })
+    }
+
+    /// Reference embedded in projects: the original file when there is one.
+    pub fn to_ref(&self) -> ShotRef {
+        match &self.raw {
+            Some(raw) => ShotRef::Embedded((**raw).clone()),
+            None => ShotRef::Parsed(Box::new((*self.data).clone())),
+        }
+    }
+}
+
+impl From<ShotData> for Shot {
+    fn from(data: ShotData) -> Self {
+        Self {
+            raw: None,
+            data: Rc::new(data),
+        }
+    }
+}
+
+/// How far a page got looking up the shot of its route.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Lookup {
+    Loading,
+    Missing,
+    Found(Shot),
+}
+
+/// Looks up the shot behind a route id: a bundled sample or a library shot.
+pub async fn find(id: String) -> Lookup {
+    if let Some(json) = sample(&id) {
+        return match serde_json::from_str(json).map(Shot::from_value) {
+            Ok(Ok(shot)) => Lookup::Found(shot),
+            _ => Lookup::Missing,
+        };
+    }
+    match get_shot(&id).await {
+        Ok(Some(shot)) => Lookup::Found(shot.data.into()),
+        Ok(None) => Lookup::Missing,
+        Err(e) => {
+            log::error!("Failed to read shot {} from the library: {}", id, e);
+            Lookup::Missing
+        }
+    }
+}
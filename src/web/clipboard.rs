@@ -0,0 +1,12 @@
+use js_sys::{Function, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Puts `text` on the clipboard. `navigator.clipboard` is still behind
+/// `web_sys_unstable_apis`, so it is looked up dynamically.
+pub fn copy_text(text: &str) -> Result<(), JsValue> {
+    let navigator = gloo_utils::window().navigator();
+    let clipboard = Reflect::get(&navigator, &"clipboard".into())?;
+    let write_text = Reflect::get(&clipboard, &"writeText".into())?.dyn_into::<Function>()?;
+    write_text.call1(&clipboard, &text.into())?;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use gloo::file::{Blob, ObjectUrl};
+use gloo::timers::callback::Timeout;
+use wasm_bindgen::JsCast;
+use web_sys::HtmlAnchorElement;
+
+/// Lets the browser save `url` (an object or data URL) as `filename`.
+pub fn download_url(filename: &str, url: &str) {
+    let anchor = gloo_utils::document()
+        .create_element("a")
+        .ok()
+        .and_then(|el| el.dyn_into::<HtmlAnchorElement>().ok());
+    match anchor {
+        Some(anchor) => {
+            anchor.set_href(url);
+            anchor.set_download(filename);
+            anchor.click();
+        }
+        None => log::error!("Failed to create download link for {}", filename),
+    }
+}
+
+pub fn download_bytes(filename: &str, mime: &str, bytes: &[u8]) {
+    let url = ObjectUrl::from(Blob::new_with_options(bytes, Some(mime)));
+    download_url(filename, &url);
+    // Give the browser a moment to start the download before revoking the URL.
+    Timeout::new(1_000, move || drop(url)).forget();
+}
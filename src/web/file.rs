@@ -0,0 +1,57 @@
+use decent_overlay_core::{fingerprint, VideoRef, FINGERPRINT_CHUNK};
+use gloo::file::{futures::read_as_bytes, futures::read_as_text, File, FileReadError};
+use web_sys::{DragEvent, FileList, HtmlInputElement};
+use yew::{Event, TargetCast};
+
+/// First file picked in the `<input type="file">` that fired `e`.
+pub fn picked_file(e: &Event) -> Option<File> {
+    let input = e.target_unchecked_into::<HtmlInputElement>();
+    let file = input.files()?.get(0)?;
+    // Let picking the same file again fire another change event.
+    input.set_value("");
+    Some(File::from(file))
+}
+
+fn files_of(list: Option<FileList>) -> Vec<File> {
+    match list {
+        Some(list) => (0..list.length())
+            .filter_map(|i| list.get(i))
+            .map(File::from)
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Every file picked in a `<input type="file" multiple>`.
+pub fn picked_files(e: &Event) -> Vec<File> {
+    let input = e.target_unchecked_into::<HtmlInputElement>();
+    let files = files_of(input.files());
+    input.set_value("");
+    files
+}
+
+/// Every file dropped onto the element; stops the browser from opening them.
+pub fn dropped_files(e: &DragEvent) -> Vec<File> {
+    e.prevent_default();
+    files_of(e.data_transfer().and_then(|transfer| transfer.files()))
+}
+
+pub async fn read_text(file: &File) -> Result<String, FileReadError> {
+    read_as_text(file).await
+}
+
+pub async fn read_bytes(file: &File) -> Result<Vec<u8>, FileReadError> {
+    read_as_bytes(file).await
+}
+
+/// Identifies a video without reading all of it.
+pub async fn video_ref(file: &File) -> Result<VideoRef, FileReadError> {
+    let size = file.size();
+    let head = read_as_bytes(&file.slice(0, FINGERPRINT_CHUNK.min(size))).await?;
+    let tail = read_as_bytes(&file.slice(size.saturating_sub(FINGERPRINT_CHUNK), size)).await?;
+    Ok(VideoRef {
+        name: file.name(),
+        size,
+        hash: fingerprint(size, &head, &tail),
+    })
+}
@@ -0,0 +1,80 @@
+//! Shot library kept in the browser's IndexedDB.
+
+use decent_overlay_core::LibraryShot;
+use indexed_db_futures::prelude::*;
+use wasm_bindgen::JsValue;
+use web_sys::DomException;
+
+const DB_NAME: &str = "decent-video-overlay";
+const DB_VERSION: u32 = 1;
+const SHOTS: &str = "shots";
+
+fn dom_error(e: DomException) -> String {
+    format!("{}: {}", e.name(), e.message())
+}
+
+async fn open() -> Result<IdbDatabase, String> {
+    let mut request = IdbDatabase::open_u32(DB_NAME, DB_VERSION).map_err(dom_error)?;
+    request.set_on_upgrade_needed(Some(|e: &IdbVersionChangeEvent| -> Result<(), JsValue> {
+        if !e.db().object_store_names().any(|name| name == SHOTS) {
+            let mut params = IdbObjectStoreParameters::new();
+            params.key_path(Some(&IdbKeyPath::str("id")));
+            e.db().create_object_store_with_params(SHOTS, &params)?;
+        }
+        Ok(())
+    }));
+    request.await.map_err(dom_error)
+}
+
+/// Adds `shots`, replacing those already stored under the same id.
+pub async fn save_shots(shots: &[LibraryShot]) -> Result<(), String> {
+    let db = open().await?;
+    let tx = db
+        .transaction_on_one_with_mode(SHOTS, IdbTransactionMode::Readwrite)
+        .map_err(dom_error)?;
+    let store = tx.object_store(SHOTS).map_err(dom_error)?;
+    for shot in shots {
+        let value = serde_wasm_bindgen::to_value(shot).map_err(|e| e.to_string())?;
+        store.put_val_owned(value).map_err(dom_error)?;
+    }
+    tx.await.into_result().map_err(dom_error)
+}
+
+pub async fn load_shots() -> Result<Vec<LibraryShot>, String> {
+    let db = open().await?;
+    let tx = db.transaction_on_one(SHOTS).map_err(dom_error)?;
+    let store = tx.object_store(SHOTS).map_err(dom_error)?;
+    let values = store
+        .get_all()
+        .map_err(dom_error)?
+        .await
+        .map_err(dom_error)?;
+    values
+        .iter()
+        .map(|value| serde_wasm_bindgen::from_value(value).map_err(|e| e.to_string()))
+        .collect()
+}
+
+pub async fn get_shot(id: &str) -> Result<Option<LibraryShot>, String> {
+    let db = open().await?;
+    let tx = db.transaction_on_one(SHOTS).map_err(dom_error)?;
+    let store = tx.object_store(SHOTS).map_err(dom_error)?;
+    let value = store
+        .get_owned(id)
+        .map_err(dom_error)?
+        .await
+        .map_err(dom_error)?;
+    value
+        .map(|value| serde_wasm_bindgen::from_value(value).map_err(|e| e.to_string()))
+        .transpose()
+}
+
+pub async fn delete_shot(id: &str) -> Result<(), String> {
+    let db = open().await?;
+    let tx = db
+        .transaction_on_one_with_mode(SHOTS, IdbTransactionMode::Readwrite)
+        .map_err(dom_error)?;
+    let store = tx.object_store(SHOTS).map_err(dom_error)?;
+    store.delete_owned(id).map_err(dom_error)?;
+    tx.await.into_result().map_err(dom_error)
+}
@@ -0,0 +1,9 @@
+mod clipboard;
+mod download;
+mod file;
+mod library;
+
+pub use clipboard::copy_text;
+pub use download::{download_bytes, download_url};
+pub use file::{dropped_files, picked_file, picked_files, read_bytes, read_text, video_ref};
+pub use library::{delete_shot, get_shot, load_shots, save_shots};